@@ -198,6 +198,7 @@ pub struct TraitData {
     pub items: Vec<(Name, AssocItemId)>,
     pub is_auto: bool,
     pub is_unsafe: bool,
+    pub is_alias: bool,
     pub visibility: RawVisibility,
     /// Whether the trait has `#[rust_skip_array_during_method_dispatch]`. `hir_ty` will ignore
     /// method calls to this trait's methods when the receiver is an array and the crate edition is
@@ -219,6 +220,7 @@ impl TraitData {
         let name = tr_def.name.clone();
         let is_auto = tr_def.is_auto;
         let is_unsafe = tr_def.is_unsafe;
+        let is_alias = tr_def.is_alias;
         let module_id = tr_loc.container;
         let visibility = item_tree[tr_def.visibility].clone();
         let skip_array_during_method_dispatch = item_tree
@@ -240,6 +242,7 @@ impl TraitData {
             items: collector.items,
             is_auto,
             is_unsafe,
+            is_alias,
             visibility,
             skip_array_during_method_dispatch,
         })
@@ -40,6 +40,9 @@ pub struct ItemScope {
     values: FxHashMap<Name, (ModuleDefId, Visibility)>,
     macros: FxHashMap<Name, (MacroId, Visibility)>,
     unresolved: FxHashSet<Name>,
+    /// Names in the type namespace that multiple glob imports bring in for different items,
+    /// together with all of these items. Only the first one of them is recorded in `types`.
+    ambiguous_glob_types: FxHashMap<Name, SmallVec<[ModuleDefId; 2]>>,
 
     /// The defs declared in this scope. Each def has a single scope where it is
     /// declared.
@@ -147,6 +150,12 @@ impl ItemScope {
         }
     }
 
+    /// Returns the names that are ambiguous between multiple glob imports in the type namespace,
+    /// together with the items they refer to.
+    pub fn ambiguous_glob_types(&self) -> impl Iterator<Item = (&Name, &[ModuleDefId])> + '_ {
+        self.ambiguous_glob_types.iter().map(|(name, defs)| (name, &**defs))
+    }
+
     pub(crate) fn type_(&self, name: &Name) -> Option<(ModuleDefId, Visibility)> {
         self.types.get(name).copied()
     }
@@ -303,6 +312,29 @@ impl ItemScope {
             }};
         }
 
+        if let Some((new, _)) = def.types {
+            match def_import_type {
+                ImportType::Glob if glob_imports.types.contains(&lookup) => {
+                    match self.types.get(&lookup.1) {
+                        Some(&(existing, _)) if existing != new => {
+                            let defs = self
+                                .ambiguous_glob_types
+                                .entry(lookup.1.clone())
+                                .or_insert_with(|| smallvec![existing]);
+                            if !defs.contains(&new) {
+                                defs.push(new);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                ImportType::Glob => {}
+                // Named imports and declarations shadow glob imports.
+                ImportType::Named => {
+                    self.ambiguous_glob_types.remove(&lookup.1);
+                }
+            }
+        }
         check_changed!(changed, (self / def).types, glob_imports[lookup], def_import_type);
         check_changed!(changed, (self / def).values, glob_imports[lookup], def_import_type);
         check_changed!(changed, (self / def).macros, glob_imports[lookup], def_import_type);
@@ -378,6 +410,7 @@ impl ItemScope {
             values,
             macros,
             unresolved,
+            ambiguous_glob_types,
             declarations,
             impls,
             unnamed_consts,
@@ -390,6 +423,7 @@ impl ItemScope {
         values.shrink_to_fit();
         macros.shrink_to_fit();
         unresolved.shrink_to_fit();
+        ambiguous_glob_types.shrink_to_fit();
         declarations.shrink_to_fit();
         impls.shrink_to_fit();
         unnamed_consts.shrink_to_fit();
@@ -666,6 +666,8 @@ pub struct Trait {
     pub generic_params: Interned<GenericParams>,
     pub is_auto: bool,
     pub is_unsafe: bool,
+    /// Whether this is a trait alias, like `trait Foo = Bar + Baz;`.
+    pub is_alias: bool,
     pub items: Box<[AssocItem]>,
     pub ast_id: FileAstId<ast::Trait>,
 }
@@ -365,6 +365,7 @@ impl<'a> Printer<'a> {
                     visibility,
                     is_auto,
                     is_unsafe,
+                    is_alias: _,
                     items,
                     generic_params,
                     ast_id: _,
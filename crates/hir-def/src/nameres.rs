@@ -314,7 +314,7 @@ impl DefMap {
         self.block.as_ref().map(|block| block.block)
     }
 
-    pub(crate) fn prelude(&self) -> Option<ModuleId> {
+    pub fn prelude(&self) -> Option<ModuleId> {
         self.prelude
     }
 
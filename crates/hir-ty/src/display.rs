@@ -105,6 +105,17 @@ pub trait HirDisplay {
         &'a self,
         db: &'a dyn HirDatabase,
         module_id: ModuleId,
+    ) -> Result<String, DisplaySourceCodeError> {
+        self.display_source_code_with_import(db, module_id, None)
+    }
+
+    /// Like `display_source_code`, but names the `imported` ADT by its name only, as it is
+    /// going to be imported into the given module along with inserting the String.
+    fn display_source_code_with_import<'a>(
+        &'a self,
+        db: &'a dyn HirDatabase,
+        module_id: ModuleId,
+        imported: Option<hir_def::AdtId>,
     ) -> Result<String, DisplaySourceCodeError> {
         let mut result = String::new();
         match self.hir_fmt(&mut HirFormatter {
@@ -114,7 +125,7 @@ pub trait HirDisplay {
             curr_size: 0,
             max_size: None,
             omit_verbose_types: false,
-            display_target: DisplayTarget::SourceCode { module_id },
+            display_target: DisplayTarget::SourceCode { module_id, imported },
         }) {
             Ok(()) => {}
             Err(HirDisplayError::FmtError) => panic!("Writing to String can't fail!"),
@@ -202,7 +213,8 @@ pub enum DisplayTarget {
     Diagnostics,
     /// Display types for inserting them in source files.
     /// The generated code should compile, so paths need to be qualified.
-    SourceCode { module_id: ModuleId },
+    /// `imported` is an ADT that is going to be imported, which is named directly.
+    SourceCode { module_id: ModuleId, imported: Option<hir_def::AdtId> },
     /// Only for test purpose to keep real types
     Test,
 }
@@ -520,15 +532,9 @@ impl HirDisplay for Ty {
             }
             TyKind::Adt(AdtId(def_id), parameters) => {
                 match f.display_target {
-                    DisplayTarget::Diagnostics | DisplayTarget::Test => {
-                        let name = match *def_id {
-                            hir_def::AdtId::StructId(it) => f.db.struct_data(it).name.clone(),
-                            hir_def::AdtId::UnionId(it) => f.db.union_data(it).name.clone(),
-                            hir_def::AdtId::EnumId(it) => f.db.enum_data(it).name.clone(),
-                        };
-                        write!(f, "{}", name)?;
-                    }
-                    DisplayTarget::SourceCode { module_id } => {
+                    DisplayTarget::SourceCode { module_id, imported }
+                        if imported != Some(*def_id) =>
+                    {
                         if let Some(path) = find_path::find_path(
                             f.db.upcast(),
                             ItemInNs::Types((*def_id).into()),
@@ -541,6 +547,15 @@ impl HirDisplay for Ty {
                             ));
                         }
                     }
+                    // The imported ADT is named directly, like in the other targets.
+                    _ => {
+                        let name = match *def_id {
+                            hir_def::AdtId::StructId(it) => f.db.struct_data(it).name.clone(),
+                            hir_def::AdtId::UnionId(it) => f.db.union_data(it).name.clone(),
+                            hir_def::AdtId::EnumId(it) => f.db.enum_data(it).name.clone(),
+                        };
+                        write!(f, "{}", name)?;
+                    }
                 }
 
                 if parameters.len(Interner) > 0 {
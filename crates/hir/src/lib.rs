@@ -45,8 +45,9 @@ use hir_def::{
     lang_item::LangItemTarget,
     nameres::{self, diagnostics::DefDiagnostic},
     per_ns::PerNs,
-    resolver::{HasResolver, Resolver},
+    resolver::{HasResolver, Resolver, TypeNs},
     src::HasSource as _,
+    type_ref::TypeBound,
     AdtId, AssocItemId, AssocItemLoc, AttrDefId, ConstId, ConstParamId, DefWithBodyId, EnumId,
     FunctionId, GenericDefId, HasModule, ImplId, ItemContainerId, LifetimeParamId,
     LocalEnumVariantId, LocalFieldId, Lookup, MacroExpander, MacroId, ModuleId, StaticId, StructId,
@@ -186,6 +187,11 @@ impl Crate {
         Module { id: def_map.module_id(def_map.root()) }
     }
 
+    /// Returns the prelude module of this crate, whose items are in scope in all of its modules.
+    pub fn prelude(self, db: &dyn HirDatabase) -> Option<Module> {
+        db.crate_def_map(self.id).prelude().map(|id| Module { id })
+    }
+
     pub fn modules(self, db: &dyn HirDatabase) -> Vec<Module> {
         let def_map = db.crate_def_map(self.id);
         def_map.modules().map(|(id, _)| def_map.module_id(id).into()).collect()
@@ -471,6 +477,12 @@ impl Module {
         Some(Module { id: def_map.module_id(parent_id) })
     }
 
+    /// Finds the module containing this one, which is the module of the surrounding block
+    /// expression for the root module of a block.
+    pub fn containing_module(self, db: &dyn HirDatabase) -> Option<Module> {
+        self.id.containing_module(db.upcast()).map(|id| Module { id })
+    }
+
     pub fn path_to_root(self, db: &dyn HirDatabase) -> Vec<Module> {
         let mut res = vec![self];
         let mut curr = self;
@@ -509,6 +521,16 @@ impl Module {
             .collect()
     }
 
+    /// Returns the names in the type namespace of this module that multiple glob imports bring in
+    /// for different items, together with these items.
+    pub fn ambiguous_glob_types(self, db: &dyn HirDatabase) -> Vec<(Name, Vec<ModuleDef>)> {
+        self.id.def_map(db.upcast())[self.id.local_id]
+            .scope
+            .ambiguous_glob_types()
+            .map(|(name, defs)| (name.clone(), defs.iter().map(|&it| it.into()).collect()))
+            .collect()
+    }
+
     pub fn diagnostics(self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
         let _p = profile::span("Module::diagnostics").detail(|| {
             format!("{:?}", self.name(db).map_or("<unknown>".into(), |name| name.to_string()))
@@ -1698,6 +1720,29 @@ impl Trait {
     pub fn is_unsafe(&self, db: &dyn HirDatabase) -> bool {
         db.trait_data(self.id).is_unsafe
     }
+
+    /// Whether this is a trait alias, like `trait Foo = Bar + Baz;`.
+    pub fn is_alias(self, db: &dyn HirDatabase) -> bool {
+        db.trait_data(self.id).is_alias
+    }
+
+    /// Whether the trait is sealed, that is it can be named from other crates but has a supertrait
+    /// that can't be, so it can't be implemented outside of its own crate.
+    pub fn is_sealed(self, db: &dyn HirDatabase) -> bool {
+        let krate = self.module(db).krate();
+        let import_map = db.import_map(krate.id);
+        let is_reachable_from_other_crates = |trait_: Trait| {
+            import_map.path_of(ItemInNs::from(ModuleDef::from(trait_)).into()).is_some()
+        };
+        if !is_reachable_from_other_crates(self) {
+            return false;
+        }
+        all_super_traits(db.upcast(), self.into())
+            .into_iter()
+            .skip(1)
+            .map(Trait::from)
+            .any(|it| it.module(db).krate() == krate && !is_reachable_from_other_crates(it))
+    }
 }
 
 impl HasVisibility for Trait {
@@ -1735,6 +1780,24 @@ impl TypeAlias {
     pub fn name(self, db: &dyn HirDatabase) -> Name {
         db.type_alias_data(self.id).name.clone()
     }
+
+    /// Returns the traits the type alias is declared to implement, as in `type Item: Copy;`.
+    pub fn trait_bounds(self, db: &dyn HirDatabase) -> Vec<Trait> {
+        let resolver = self.id.resolver(db.upcast());
+        db.type_alias_data(self.id)
+            .bounds
+            .iter()
+            .filter_map(|bound| match &**bound {
+                TypeBound::Path(path, _) | TypeBound::ForLifetime(_, path) => {
+                    match resolver.resolve_path_in_type_ns_fully(db.upcast(), path.mod_path())? {
+                        TypeNs::TraitId(it) => Some(Trait::from(it)),
+                        _ => None,
+                    }
+                }
+                TypeBound::Lifetime(_) | TypeBound::Error => None,
+            })
+            .collect()
+    }
 }
 
 impl HasVisibility for TypeAlias {
@@ -1755,6 +1818,14 @@ impl BuiltinType {
         BuiltinType { inner: hir_def::builtin_type::BuiltinType::Str }
     }
 
+    pub fn usize() -> BuiltinType {
+        BuiltinType {
+            inner: hir_def::builtin_type::BuiltinType::Uint(
+                hir_def::builtin_type::BuiltinUint::Usize,
+            ),
+        }
+    }
+
     pub fn ty(self, db: &dyn HirDatabase) -> Type {
         Type::new_for_crate(db.crate_graph().iter().next().unwrap(), TyBuilder::builtin(self.inner))
     }
@@ -3112,6 +3183,17 @@ impl Type {
         );
     }
 
+    /// Renders the type as source code that is valid in `module` once `imported` has been
+    /// imported into it, naming `imported` without a path wherever it occurs.
+    pub fn display_source_code_with_import(
+        &self,
+        db: &dyn HirDatabase,
+        module: Module,
+        imported: Adt,
+    ) -> Option<String> {
+        self.ty.display_source_code_with_import(db, module.id, Some(imported.into())).ok()
+    }
+
     pub fn as_adt(&self) -> Option<Adt> {
         let (adt, _subst) = self.ty.as_adt()?;
         Some(adt.into())
@@ -4,10 +4,12 @@ pub(crate) mod attribute;
 pub(crate) mod dot;
 pub(crate) mod expr;
 pub(crate) mod extern_abi;
+pub(crate) mod extern_crate;
 pub(crate) mod field;
 pub(crate) mod flyimport;
 pub(crate) mod fn_param;
 pub(crate) mod format_string;
+pub(crate) mod fragment_specifier;
 pub(crate) mod item_list;
 pub(crate) mod keyword;
 pub(crate) mod lifetime;
@@ -20,16 +22,16 @@ pub(crate) mod r#type;
 pub(crate) mod use_;
 pub(crate) mod vis;
 
-use std::iter;
+use std::{fmt, iter};
 
-use hir::{db::HirDatabase, known, ScopeDef};
+use hir::{db::HirDatabase, known, ModuleSource, ScopeDef};
 use ide_db::SymbolKind;
 
 use crate::{
     context::Visible,
     item::Builder,
     render::{
-        const_::render_const,
+        const_::{render_const, render_qualified_const},
         function::{render_fn, render_method},
         literal::{render_struct_literal, render_variant_lit},
         macro_::render_macro,
@@ -58,14 +60,43 @@ fn module_or_fn_macro(db: &dyn HirDatabase, def: ScopeDef) -> Option<ScopeDef> {
     }
 }
 
+/// Receives completion items as soon as they are produced, allowing clients to render the first
+/// results before all candidates have been computed.
+pub trait CompletionSink {
+    fn accept(&mut self, item: CompletionItem);
+
+    /// Whether the sink doesn't want any more items, providers stop computing candidates once
+    /// this returns `true`.
+    fn is_saturated(&self) -> bool {
+        false
+    }
+}
+
+impl CompletionSink for Vec<CompletionItem> {
+    fn accept(&mut self, item: CompletionItem) {
+        self.push(item)
+    }
+}
+
 /// Represents an in-progress set of completions being built.
-#[derive(Debug, Default)]
-pub struct Completions {
+#[derive(Default)]
+pub struct Completions<'a> {
     buf: Vec<CompletionItem>,
+    /// Receives the items instead of `buf` if set.
+    sink: Option<&'a mut dyn CompletionSink>,
+}
+
+impl fmt::Debug for Completions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Completions")
+            .field("buf", &self.buf)
+            .field("sink", &self.sink.is_some())
+            .finish()
+    }
 }
 
-impl From<Completions> for Vec<CompletionItem> {
-    fn from(val: Completions) -> Self {
+impl From<Completions<'_>> for Vec<CompletionItem> {
+    fn from(val: Completions<'_>) -> Self {
         val.buf
     }
 }
@@ -78,14 +109,27 @@ impl Builder {
     }
 }
 
-impl Completions {
+impl<'a> Completions<'a> {
+    pub(crate) fn with_sink(sink: &'a mut dyn CompletionSink) -> Completions<'a> {
+        Completions { buf: Vec::new(), sink: Some(sink) }
+    }
+
+    /// Whether the items the sink asked for have all been produced already.
+    pub(crate) fn is_saturated(&self) -> bool {
+        self.sink.as_ref().map_or(false, |sink| sink.is_saturated())
+    }
+
     fn add(&mut self, item: CompletionItem) {
-        self.buf.push(item)
+        match &mut self.sink {
+            Some(sink) if sink.is_saturated() => (),
+            Some(sink) => sink.accept(item),
+            None => self.buf.push(item),
+        }
     }
 
     fn add_opt(&mut self, item: Option<CompletionItem>) {
         if let Some(item) = item {
-            self.buf.push(item)
+            self.add(item)
         }
     }
 
@@ -97,13 +141,22 @@ impl Completions {
         items.into_iter().for_each(|item| self.add(item.into()))
     }
 
-    pub(crate) fn add_keyword(&mut self, ctx: &CompletionContext, keyword: &'static str) {
+    pub(crate) fn add_keyword(&mut self, ctx: &CompletionContext, keyword: &str) {
         let item = CompletionItem::new(CompletionItemKind::Keyword, ctx.source_range(), keyword);
         item.add_to(self);
     }
 
     pub(crate) fn add_nameref_keywords_with_colon(&mut self, ctx: &CompletionContext) {
         ["self::", "super::", "crate::"].into_iter().for_each(|kw| self.add_keyword(ctx, kw));
+        // `super::` only goes up a single module, so offer going further up in nested modules.
+        // Block expressions aren't modules `super` can refer to.
+        let depth = iter::successors(Some(ctx.module), |it| it.containing_module(ctx.db))
+            .filter(|it| !matches!(it.definition_source(ctx.db).value, ModuleSource::BlockExpr(_)))
+            .count()
+            .saturating_sub(1);
+        for n in 2..=depth {
+            self.add_keyword(ctx, &"super::".repeat(n));
+        }
     }
 
     pub(crate) fn add_nameref_keywords(&mut self, ctx: &CompletionContext) {
@@ -131,12 +184,29 @@ impl Completions {
     }
 
     pub(crate) fn add_crate_roots(&mut self, ctx: &CompletionContext) {
+        // A crate may be reachable under multiple names, so only list it once, preferring its
+        // canonical name over aliases.
+        let mut roots: Vec<(hir::Name, hir::Module)> = Vec::new();
         ctx.process_all_names(&mut |name, res| match res {
             ScopeDef::ModuleDef(hir::ModuleDef::Module(m)) if m.is_crate_root(ctx.db) => {
-                self.add_resolution(ctx, name, res);
+                match roots.iter_mut().find(|(_, it)| *it == m) {
+                    Some(root) => {
+                        let is_canonical = m
+                            .krate()
+                            .display_name(ctx.db)
+                            .map_or(false, |it| **it.crate_name() == *name.to_smol_str());
+                        if is_canonical {
+                            root.0 = name;
+                        }
+                    }
+                    None => roots.push((name, m)),
+                }
             }
             _ => (),
         });
+        for (name, m) in roots {
+            self.add_resolution(ctx, name, ScopeDef::ModuleDef(m.into()));
+        }
     }
 
     pub(crate) fn add_resolution(
@@ -241,6 +311,24 @@ impl Completions {
         ));
     }
 
+    pub(crate) fn add_qualified_const(
+        &mut self,
+        ctx: &CompletionContext,
+        konst: hir::Const,
+        qualifier: &str,
+    ) {
+        let is_private_editable = match ctx.is_visible(&konst) {
+            Visible::Yes => false,
+            Visible::Editable => true,
+            Visible::No => return,
+        };
+        self.add_opt(render_qualified_const(
+            RenderContext::new(ctx).private_editable(is_private_editable),
+            konst,
+            qualifier,
+        ));
+    }
+
     pub(crate) fn add_type_alias(&mut self, ctx: &CompletionContext, type_alias: hir::TypeAlias) {
         let is_private_editable = match ctx.is_visible(&type_alias) {
             Visible::Yes => false,
@@ -258,7 +346,15 @@ impl Completions {
         ctx: &CompletionContext,
         type_alias: hir::TypeAlias,
     ) {
-        self.add_opt(render_type_alias_with_eq(RenderContext::new(ctx), type_alias));
+        let is_private_editable = match ctx.is_visible(&type_alias) {
+            Visible::Yes => false,
+            Visible::Editable => true,
+            Visible::No => return,
+        };
+        self.add_opt(render_type_alias_with_eq(
+            RenderContext::new(ctx).private_editable(is_private_editable),
+            type_alias,
+        ));
     }
 
     pub(crate) fn add_qualified_enum_variant(
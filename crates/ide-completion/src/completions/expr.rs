@@ -2,10 +2,11 @@
 
 use hir::ScopeDef;
 use ide_db::FxHashSet;
-use syntax::T;
+use syntax::{ast, AstNode, T};
 
 use crate::{
     context::{NameRefContext, PathCompletionCtx, PathKind, PathQualifierCtx},
+    patterns::ImmediateLocation,
     CompletionContext, Completions,
 };
 
@@ -68,9 +69,13 @@ pub(crate) fn complete_expr_path(acc: &mut Completions, ctx: &CompletionContext)
                 Some(it) => it,
                 None => return,
             };
-            // Add associated types on type parameters and `Self`.
+            // Add associated types on type parameters and `Self`. Bounds may reach the same trait
+            // multiple times through their supertraits, so deduplicate the associated items.
+            let mut seen = FxHashSet::default();
             ctx.scope.assoc_type_shorthand_candidates(resolution, |_, alias| {
-                acc.add_type_alias(ctx, alias);
+                if seen.insert(hir::AssocItem::TypeAlias(alias)) {
+                    acc.add_type_alias(ctx, alias);
+                }
                 None::<()>
             });
             match resolution {
@@ -147,7 +152,6 @@ pub(crate) fn complete_expr_path(acc: &mut Completions, ctx: &CompletionContext)
                     if let Some(hir::Adt::Enum(e)) = ty.as_adt() {
                         add_enum_variants(acc, ctx, e);
                     }
-                    let mut seen = FxHashSet::default();
                     ty.iterate_path_candidates(
                         ctx.db,
                         &ctx.scope,
@@ -213,6 +217,7 @@ pub(crate) fn complete_expr_path(acc: &mut Completions, ctx: &CompletionContext)
                     acc.add_resolution(ctx, name, def);
                 }
             });
+            add_array_len_assoc_consts(acc, ctx);
 
             if !is_func_update {
                 let mut add_keyword = |kw, snippet| acc.add_keyword_snippet(ctx, kw, snippet);
@@ -283,3 +288,28 @@ fn add_assoc_item(acc: &mut Completions, ctx: &CompletionContext, item: hir::Ass
 fn add_enum_variants(acc: &mut Completions, ctx: &CompletionContext, e: hir::Enum) {
     e.variants(ctx.db).into_iter().for_each(|variant| acc.add_enum_variant(ctx, variant, None));
 }
+
+/// Adds the `usize` associated consts of the element type when completing the length of an array
+/// type, as in `[Foo; $0]`.
+fn add_array_len_assoc_consts(acc: &mut Completions, ctx: &CompletionContext) {
+    let array = match &ctx.completion_location {
+        Some(ImmediateLocation::ArrayLen(it)) => it,
+        _ => return,
+    };
+    let (qualifier, ty) = match array.ty() {
+        Some(ast::Type::PathType(it)) => match (it.path(), ctx.sema.resolve_type(&it.into())) {
+            (Some(path), Some(ty)) => (path.syntax().text().to_string(), ty),
+            _ => return,
+        },
+        _ => return,
+    };
+    ty.iterate_assoc_items(ctx.db, ctx.krate, |item| {
+        if let hir::AssocItem::Const(konst) = item {
+            if konst.ty(ctx.db).is_usize() {
+                cov_mark::hit!(complete_array_len_assoc_const);
+                acc.add_qualified_const(ctx, konst, &qualifier);
+            }
+        }
+        None::<()>
+    });
+}
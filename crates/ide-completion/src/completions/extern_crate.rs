@@ -0,0 +1,72 @@
+//! Completes the crate names of `extern crate` items.
+use crate::{
+    context::{CompletionContext, NameRefContext},
+    CompletionItem, CompletionItemKind, Completions,
+};
+
+pub(crate) fn complete_extern_crate(acc: &mut Completions, ctx: &CompletionContext) -> Option<()> {
+    match ctx.nameref_ctx() {
+        Some(NameRefContext { extern_crate: Some(_), .. }) => (),
+        _ => return None,
+    }
+    // Only dependencies can be declared here, so this never lists the current crate.
+    for dep in ctx.krate.dependencies(ctx.db) {
+        let root = dep.krate.root_module(ctx.db);
+        acc.add_resolution(ctx, dep.name.clone(), hir::ScopeDef::ModuleDef(root.into()));
+        if let Some(cap) = ctx.config.snippet_cap {
+            let mut item = CompletionItem::new(
+                CompletionItemKind::Snippet,
+                ctx.source_range(),
+                format!("{} as …", dep.name),
+            );
+            item.insert_snippet(cap, format!("{0} as ${{1:{0}}}", dep.name));
+            item.add_to(acc);
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_dependencies() {
+        check(
+            r#"
+//- /main.rs crate:main deps:foo,bar
+extern crate $0;
+//- /foo/lib.rs crate:foo
+//- /bar/lib.rs crate:bar
+"#,
+            expect![[r#"
+                md bar
+                md foo
+                sn bar as …
+                sn foo as …
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_rename_snippet() {
+        check_edit(
+            "foo as …",
+            r#"
+//- /main.rs crate:main deps:foo
+extern crate $0;
+//- /foo/lib.rs crate:foo
+"#,
+            r#"
+extern crate foo as ${1:foo};
+"#,
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Completes the fragment specifiers of `macro_rules!` metavariables.
+use crate::{
+    completions::Completions,
+    context::{CompletionContext, IdentContext},
+    CompletionItem, CompletionItemKind,
+};
+
+const FRAGMENT_SPECIFIERS: &[&str] = &[
+    "block",
+    "expr",
+    "ident",
+    "item",
+    "lifetime",
+    "literal",
+    "meta",
+    "pat",
+    "pat_param",
+    "path",
+    "stmt",
+    "tt",
+    "ty",
+    "vis",
+];
+
+pub(crate) fn complete_fragment_specifier(acc: &mut Completions, ctx: &CompletionContext) {
+    if !matches!(ctx.ident_ctx, IdentContext::FragmentSpecifier) {
+        return;
+    }
+    for &specifier in FRAGMENT_SPECIFIERS {
+        CompletionItem::new(CompletionItemKind::Keyword, ctx.source_range(), specifier).add_to(acc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_fragment_specifiers() {
+        check(
+            r#"
+macro_rules! m {
+    ($x:$0) => {};
+}
+"#,
+            expect![[r#"
+                kw block
+                kw expr
+                kw ident
+                kw item
+                kw lifetime
+                kw literal
+                kw meta
+                kw pat
+                kw pat_param
+                kw path
+                kw stmt
+                kw tt
+                kw ty
+                kw vis
+            "#]],
+        );
+        check_edit(
+            "ty",
+            r#"
+macro_rules! m {
+    ($($x:t$0),*) => {};
+}
+"#,
+            r#"
+macro_rules! m {
+    ($($x:ty),*) => {};
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn only_completes_in_matchers() {
+        check(
+            r#"
+macro_rules! m {
+    ($x:ty) => { $x:$0 };
+}
+"#,
+            expect![[]],
+        );
+    }
+}
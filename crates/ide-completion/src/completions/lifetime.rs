@@ -184,6 +184,35 @@ fn foo2<'lifetime, T>() where T: Trait<'a$0> {}
         );
     }
 
+    #[test]
+    fn complete_static_lifetime_without_lifetimes_in_scope() {
+        check(
+            r#"
+fn foo(_: &'$0 str) {}
+"#,
+            expect![[r#"
+                lt 'static
+            "#]],
+        );
+        check(
+            r#"
+struct Foo<'lt>(&'lt ());
+fn foo(_: Foo<'$0>) {}
+"#,
+            expect![[r#"
+                lt 'static
+            "#]],
+        );
+        check(
+            r#"
+fn foo<T>() where T: '$0 {}
+"#,
+            expect![[r#"
+                lt 'static
+            "#]],
+        );
+    }
+
     #[test]
     fn dont_complete_lifetime_in_assoc_ty_bound() {
         check(
@@ -1,12 +1,15 @@
 //! This file provides snippet completions, like `pd` => `eprintln!(...)`.
 
-use hir::Documentation;
-use ide_db::{imports::insert_use::ImportScope, SnippetCap};
+use hir::{Documentation, ScopeDef};
+use ide_db::{imports::insert_use::ImportScope, FxHashSet, SnippetCap};
+use syntax::{SyntaxKind, T};
 
 use crate::{
     context::{ItemListKind, PathCompletionCtx, PathKind},
     item::Builder,
-    CompletionContext, CompletionItem, CompletionItemKind, Completions, SnippetScope,
+    patterns::{ImmediateLocation, TypeAnnotation},
+    CompletionContext, CompletionItem, CompletionItemKind, CompletionRelevance, Completions,
+    SnippetScope,
 };
 
 fn snippet(ctx: &CompletionContext, cap: SnippetCap, label: &str, snippet: &str) -> Builder {
@@ -41,6 +44,86 @@ pub(crate) fn complete_expr_snippet(acc: &mut Completions, ctx: &CompletionConte
     }
 }
 
+pub(crate) fn complete_type_snippet(acc: &mut Completions, ctx: &CompletionContext) {
+    let &in_impl_header = match ctx.path_context() {
+        Some(PathCompletionCtx {
+            is_absolute_path: false,
+            qualifier: None,
+            kind: PathKind::Type { in_impl_header, .. },
+            ..
+        }) => in_impl_header,
+        _ => return,
+    };
+    // Only traits are allowed in bounds and negative impls.
+    if let Some(ImmediateLocation::TypeBound) = ctx.completion_location {
+        // Higher-ranked lifetimes can only be introduced at the start of a bound.
+        // Lifetimes can only be bounded by lifetimes.
+        let at_bound_start = ctx.bounded_lifetime().is_none()
+            && ctx.previous_token.as_ref().map_or(false, |it| match it.kind() {
+                T![:] => it.parent().map_or(false, |it| it.kind() != SyntaxKind::LIFETIME_PARAM),
+                T![+] | T![dyn] | T![impl] => true,
+                _ => false,
+            });
+        if let Some(cap) = ctx.config.snippet_cap.filter(|_| at_bound_start) {
+            cov_mark::hit!(complete_hrtb_snippet);
+            snippet(ctx, cap, "for<'a>", "for<${1:'a}> $0").add_to(acc);
+        }
+        return;
+    }
+    if ctx.previous_token_is(T![!]) {
+        return;
+    }
+    let cap = match ctx.config.snippet_cap {
+        Some(it) => it,
+        None => return,
+    };
+
+    if let Some(ImmediateLocation::TypeAnnotation(TypeAnnotation::SelfParam(_))) =
+        ctx.completion_location
+    {
+        // Only `Self` and pointers to it are valid receiver types.
+        for receiver in ["Self", "&Self", "&mut Self"] {
+            snippet(ctx, cap, receiver, receiver).add_to(acc);
+        }
+        // The smart pointers are only offered if they can be named as is.
+        const WRAPPED: [(&str, &str); 4] = [
+            ("Box", "Box<Self>"),
+            ("Pin", "Pin<&mut Self>"),
+            ("Rc", "Rc<Self>"),
+            ("Arc", "Arc<Self>"),
+        ];
+        let mut wrappers_in_scope = FxHashSet::default();
+        ctx.process_all_names(&mut |name, def| {
+            if let ScopeDef::ModuleDef(hir::ModuleDef::Adt(_)) = def {
+                wrappers_in_scope.insert(name.to_smol_str());
+            }
+        });
+        for (wrapper, receiver) in WRAPPED {
+            if wrappers_in_scope.contains(wrapper) {
+                snippet(ctx, cap, receiver, receiver).add_to(acc);
+            }
+        }
+        return;
+    }
+
+    // Only a trait can come before the `for` of an impl header.
+    let before_impl_for = ctx.impl_def.as_ref().map_or(false, |impl_| {
+        impl_.for_token().map_or(true, |it| ctx.position.offset <= it.text_range().start())
+    });
+    if in_impl_header && before_impl_for && !ctx.expects_generic_arg() {
+        return;
+    }
+    let relevance = CompletionRelevance { is_syntax_snippet: true, ..Default::default() };
+
+    let mut item = snippet(ctx, cap, "tuple", "(${1:T}, ${2:U})");
+    item.detail("(T, U)").set_relevance(relevance);
+    item.add_to(acc);
+
+    let mut item = snippet(ctx, cap, "array", "[${1:T}; ${2:N}]");
+    item.detail("[T; N]").set_relevance(relevance);
+    item.add_to(acc);
+}
+
 pub(crate) fn complete_item_snippet(acc: &mut Completions, ctx: &CompletionContext) {
     let path_kind = match ctx.path_context() {
         Some(PathCompletionCtx {
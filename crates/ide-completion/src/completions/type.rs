@@ -1,31 +1,60 @@
 //! Completion of names from the current scope in type position.
 
-use hir::{HirDisplay, ScopeDef};
-use ide_db::FxHashSet;
-use syntax::{ast, AstNode};
+use hir::{AsAssocItem, HasAttrs, HasVisibility, HirDisplay, ModPath, ScopeDef};
+use ide_db::{
+    base_db::Edition,
+    imports::import_assets::{LocatedImport, NameToImport},
+    items_locator::{self, AssocItemSearch},
+    FxHashSet, RootDatabase,
+};
+use syntax::{
+    ast::{self, HasGenericParams, HasTypeBounds},
+    AstNode, SyntaxKind, T,
+};
 
 use crate::{
-    context::{PathCompletionCtx, PathKind, PathQualifierCtx},
+    context::{PathCompletionCtx, PathKind, PathQualifierCtx, Visible},
     patterns::{ImmediateLocation, TypeAnnotation},
-    render::render_type_inference,
-    CompletionContext, Completions,
+    render::{
+        render_resolution, render_resolution_with_import, render_type_inference,
+        render_wrapped_type_inference, RenderContext,
+    },
+    CompletionContext, CompletionItem, CompletionItemKind, Completions,
 };
 
 pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext) {
     let _p = profile::span("complete_type_path");
 
-    let (&is_absolute_path, qualifier) = match ctx.path_context() {
+    let (&is_absolute_path, qualifier, &in_impl_header, &in_where_pred) = match ctx.path_context() {
         Some(PathCompletionCtx {
-            kind: PathKind::Type { .. },
+            kind: PathKind::Type { in_impl_header, in_where_pred, .. },
             is_absolute_path,
             qualifier,
             ..
-        }) => (is_absolute_path, qualifier),
+        }) => (is_absolute_path, qualifier, in_impl_header, in_where_pred),
         _ => return,
     };
 
+    // Only traits can follow `impl`/`dyn` or appear in a bound list.
+    let in_type_bound = matches!(ctx.completion_location, Some(ImmediateLocation::TypeBound));
+
+    // The trait being implemented makes no sense as a type in its own impl header.
+    let implemented_trait = if in_impl_header { implemented_trait(ctx) } else { None };
+
     let scope_def_applicable = |def| {
         use hir::{GenericParam::*, ModuleDef::*};
+        if let ScopeDef::ModuleDef(def) = def {
+            if is_from_excluded_crate(ctx, def) {
+                return false;
+            }
+        }
+        if in_type_bound {
+            return match def {
+                ScopeDef::ModuleDef(Macro(mac)) => mac.is_fn_like(ctx.db),
+                ScopeDef::ModuleDef(Trait(_) | Module(_)) => true,
+                _ => false,
+            };
+        }
         match def {
             ScopeDef::GenericParam(LifetimeParam(_)) | ScopeDef::Label(_) => false,
             // no values in type places
@@ -34,9 +63,9 @@ pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext)
             ScopeDef::ModuleDef(Const(_)) | ScopeDef::GenericParam(ConstParam(_)) => {
                 ctx.expects_generic_arg()
             }
-            ScopeDef::ImplSelfType(_) => {
-                !ctx.previous_token_is(syntax::T![impl]) && !ctx.previous_token_is(syntax::T![for])
-            }
+            // `Self` is not available in the impl header itself
+            ScopeDef::ImplSelfType(_) => !in_impl_header,
+            ScopeDef::ModuleDef(Trait(it)) if implemented_trait == Some(it) => false,
             // Don't suggest attribute macros and derives.
             ScopeDef::ModuleDef(Macro(mac)) => mac.is_fn_like(ctx.db),
             // Type things are fine
@@ -48,7 +77,7 @@ pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext)
     };
 
     match qualifier {
-        Some(PathQualifierCtx { is_infer_qualifier, resolution, .. }) => {
+        Some(PathQualifierCtx { is_infer_qualifier, resolution, path, .. }) => {
             if *is_infer_qualifier {
                 ctx.traits_in_scope()
                     .0
@@ -57,22 +86,74 @@ pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext)
                     .for_each(|item| add_assoc_item(acc, ctx, item));
                 return;
             }
+            let projection;
             let resolution = match resolution {
                 Some(it) => it,
-                None => return,
+                None => match ctx.resolve_assoc_type_shorthand(path) {
+                    Some(it) => {
+                        cov_mark::hit!(resolve_assoc_type_shorthand_qualifier);
+                        projection = hir::PathResolution::Def(hir::ModuleDef::TypeAlias(it));
+                        &projection
+                    }
+                    None => return,
+                },
             };
             // Add associated types on type parameters and `Self`.
+            let mut seen = FxHashSet::default();
+            // Bounds may reach the same trait multiple times through their supertraits, so
+            // deduplicate the associated items gathered here and below.
             ctx.scope.assoc_type_shorthand_candidates(resolution, |_, alias| {
-                acc.add_type_alias(ctx, alias);
+                let item = hir::AssocItem::TypeAlias(alias);
+                if seen.insert(item) {
+                    add_assoc_item(acc, ctx, item);
+                }
                 None::<()>
             });
 
             match resolution {
                 hir::PathResolution::Def(hir::ModuleDef::Module(module)) => {
+                    // Items of a module can't be named through it if the module itself is not
+                    // visible, even if they are public.
+                    if let Visible::No = ctx.is_module_visible(*module) {
+                        return;
+                    }
+                    // Modules like preludes can have a huge amount of items, so skip the ones
+                    // the client will filter out anyways before rendering them.
+                    let typed_prefix = typed_prefix(ctx);
                     let module_scope = module.scope(ctx.db, Some(ctx.module));
                     for (name, def) in module_scope {
+                        if acc.is_saturated() {
+                            return;
+                        }
+                        if !fuzzy_matches(&name.to_smol_str(), typed_prefix) {
+                            continue;
+                        }
                         if scope_def_applicable(def) {
-                            acc.add_resolution(ctx, name, def);
+                            add_resolution_transliterated(acc, ctx, name, def);
+                        }
+                    }
+                }
+                hir::PathResolution::Def(hir::ModuleDef::TypeAlias(alias))
+                    if alias
+                        .as_assoc_item(ctx.db)
+                        .and_then(|it| it.containing_trait(ctx.db))
+                        .is_some() =>
+                {
+                    // `T::Assoc::$0` projects through the bounds of `Assoc` as we don't know its
+                    // actual type.
+                    let items: Vec<_> = assoc_type_bounds(ctx, *alias)
+                        .into_iter()
+                        .flat_map(|trait_| trait_.items_with_supertraits(ctx.db))
+                        .filter(|item| !matches!(item, hir::AssocItem::Function(_)))
+                        .collect();
+                    if items.is_empty() {
+                        // Bounds like `Sized` have no items to offer.
+                        cov_mark::hit!(projection_bounds_without_items);
+                    }
+                    for item in items {
+                        if seen.insert(item) {
+                            cov_mark::hit!(complete_assoc_type_of_projection);
+                            add_assoc_item(acc, ctx, item);
                         }
                     }
                 }
@@ -81,6 +162,10 @@ pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext)
                     | hir::ModuleDef::TypeAlias(_)
                     | hir::ModuleDef::BuiltinType(_)),
                 ) => {
+                    if let hir::ModuleDef::Adt(hir::Adt::Enum(_)) = def {
+                        // Enum variants are not types, so they can't be named in type position.
+                        cov_mark::hit!(no_enum_variants_in_type_position);
+                    }
                     let ty = match def {
                         hir::ModuleDef::Adt(adt) => adt.ty(ctx.db),
                         hir::ModuleDef::TypeAlias(a) => a.ty(ctx.db),
@@ -98,37 +183,60 @@ pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext)
                         Some(ctx.module),
                         None,
                         |item| {
-                            add_assoc_item(acc, ctx, item);
+                            if seen.insert(item) {
+                                add_assoc_item(acc, ctx, item);
+                            }
                             None::<()>
                         },
                     );
 
-                    // Iterate assoc types separately
+                    // Iterate assoc items separately, skipping the ones already found as path
+                    // candidates.
                     ty.iterate_assoc_items(ctx.db, ctx.krate, |item| {
-                        if let hir::AssocItem::TypeAlias(ty) = item {
-                            acc.add_type_alias(ctx, ty)
+                        if seen.insert(item) {
+                            add_assoc_item(acc, ctx, item);
                         }
                         None::<()>
                     });
+
+                    // Trait objects, like an alias to `dyn Trait`, carry the items of their
+                    // principal trait.
+                    if let Some(trait_) = ty.as_dyn_trait() {
+                        for item in trait_.items_with_supertraits(ctx.db) {
+                            if seen.insert(item) {
+                                cov_mark::hit!(complete_assoc_item_of_dyn_trait);
+                                add_assoc_item(acc, ctx, item);
+                            }
+                        }
+                    }
                 }
                 hir::PathResolution::Def(hir::ModuleDef::Trait(t)) => {
-                    // Handles `Trait::assoc` as well as `<Ty as Trait>::assoc`.
-                    for item in t.items(ctx.db) {
-                        add_assoc_item(acc, ctx, item);
+                    // Handles `Trait::assoc` as well as `<Ty as Trait>::assoc`, which can also
+                    // name the associated items of the supertraits.
+                    for item in t.items_with_supertraits(ctx.db) {
+                        if seen.insert(item) {
+                            add_assoc_item(acc, ctx, item);
+                        }
                     }
                 }
                 hir::PathResolution::TypeParam(_) | hir::PathResolution::SelfType(_) => {
                     let ty = match resolution {
+                        // `Self` within a trait, like in the body of a default method, is the
+                        // trait's implicit type parameter rather than an impl's self type.
+                        hir::PathResolution::TypeParam(param) if param.is_implicit(ctx.db) => {
+                            cov_mark::hit!(complete_assoc_items_of_trait_self);
+                            param.ty(ctx.db)
+                        }
                         hir::PathResolution::TypeParam(param) => param.ty(ctx.db),
                         hir::PathResolution::SelfType(impl_def) => impl_def.self_ty(ctx.db),
                         _ => return,
                     };
 
-                    let mut seen = FxHashSet::default();
+                    let traits_in_scope = ctx.traits_in_scope().0;
                     ty.iterate_path_candidates(
                         ctx.db,
                         &ctx.scope,
-                        &ctx.traits_in_scope().0,
+                        &traits_in_scope,
                         Some(ctx.module),
                         None,
                         |item| {
@@ -140,73 +248,778 @@ pub(crate) fn complete_type_path(acc: &mut Completions, ctx: &CompletionContext)
                             None::<()>
                         },
                     );
+
+                    // Associated types of traits implemented through blanket impls are not found
+                    // via the bounds of the type, so check the traits in scope for those.
+                    for trait_ in traits_in_scope.into_iter().map(hir::Trait::from) {
+                        // We can't check generic traits without knowing their arguments.
+                        let is_generic = hir::GenericDef::from(trait_)
+                            .params(ctx.db)
+                            .into_iter()
+                            .any(|param| match param {
+                                hir::GenericParam::TypeParam(it) => !it.is_implicit(ctx.db),
+                                hir::GenericParam::ConstParam(_) => true,
+                                hir::GenericParam::LifetimeParam(_) => false,
+                            });
+                        if is_generic || !ty.impls_trait(ctx.db, trait_, &[]) {
+                            continue;
+                        }
+                        for item in trait_.items(ctx.db) {
+                            if seen.insert(item) {
+                                cov_mark::hit!(complete_assoc_type_of_blanket_impl);
+                                add_assoc_item(acc, ctx, item);
+                            }
+                        }
+                    }
                 }
                 _ => (),
             }
         }
-        None if is_absolute_path => acc.add_crate_roots(ctx),
+        None if is_absolute_path => {
+            acc.add_crate_roots(ctx);
+            // Absolute paths start at the crate root on the 2015 edition, not just at the extern
+            // prelude.
+            if ctx.krate.edition(ctx.db) == Edition::Edition2015 {
+                cov_mark::hit!(complete_crate_root_items_in_2015_absolute_path);
+                let root = ctx.krate.root_module(ctx.db);
+                for (name, def) in root.scope(ctx.db, Some(ctx.module)) {
+                    let is_crate_root = matches!(
+                        def,
+                        ScopeDef::ModuleDef(hir::ModuleDef::Module(it)) if it.is_crate_root(ctx.db)
+                    );
+                    if !is_crate_root && scope_def_applicable(def) {
+                        acc.add_resolution(ctx, name, def);
+                    }
+                }
+            }
+        }
+        None if in_type_bound && ctx.bounded_lifetime().is_some() => {
+            cov_mark::hit!(complete_lifetimes_in_lifetime_bound);
+            let bounded = ctx.bounded_lifetime().map(|it| it.text().to_string());
+            ctx.process_all_names_raw(&mut |name, res| {
+                if let ScopeDef::GenericParam(hir::GenericParam::LifetimeParam(_)) = res {
+                    // A lifetime trivially outlives itself.
+                    if bounded.as_deref() != Some(&*name.to_smol_str()) {
+                        acc.add_lifetime(ctx, name);
+                    }
+                }
+            });
+            acc.add_lifetime(ctx, hir::known::STATIC_LIFETIME);
+        }
+        None if (in_impl_header || in_type_bound) && ctx.previous_token_is(T![!]) => {
+            // Only auto traits can be implemented or bounded negatively.
+            cov_mark::hit!(complete_negative_trait);
+            ctx.process_all_names(&mut |name, def| match def {
+                ScopeDef::ModuleDef(hir::ModuleDef::Trait(it)) if !it.is_auto(ctx.db) => (),
+                ScopeDef::ModuleDef(hir::ModuleDef::Trait(_) | hir::ModuleDef::Module(_)) => {
+                    acc.add_resolution(ctx, name, def)
+                }
+                _ => (),
+            });
+        }
         None => {
             acc.add_nameref_keywords_with_colon(ctx);
-            if let Some(ImmediateLocation::TypeBound) = &ctx.completion_location {
+            if let Some(ImmediateLocation::TypeAnnotation(TypeAnnotation::RetType(_))) =
+                ctx.completion_location
+            {
+                // The never type is only really useful as the return type of diverging functions.
+                acc.add_keyword(ctx, "!");
+            }
+            // `dyn` only became a proper keyword in the 2018 edition, bare trait objects are the
+            // norm in 2015 edition code. Trait objects make no sense as the trait or self type of
+            // impl headers and the subjects of where predicates either.
+            let allows_trait_object =
+                !in_type_bound && !in_where_pred && (!in_impl_header || ctx.expects_generic_arg());
+            if allows_trait_object && ctx.krate.edition(ctx.db) >= Edition::Edition2018 {
+                acc.add_keyword(ctx, "dyn");
+            }
+            if allows_inferred_type(ctx) {
+                cov_mark::hit!(complete_inferred_type_placeholder);
+                acc.add_keyword(ctx, "_");
+            }
+            if in_ref_type_lifetime_position(ctx) {
+                cov_mark::hit!(complete_lifetime_in_ref_type);
+                add_lifetimes(acc, ctx);
+            }
+            if in_type_bound {
+                let in_dyn = in_dyn_bound(ctx);
+                let after_dyn_principal = in_dyn && after_dyn_principal_trait(ctx);
                 ctx.process_all_names(&mut |name, res| {
-                    let add_resolution = match res {
-                        ScopeDef::ModuleDef(hir::ModuleDef::Macro(mac)) => mac.is_fn_like(ctx.db),
-                        ScopeDef::ModuleDef(
-                            hir::ModuleDef::Trait(_) | hir::ModuleDef::Module(_),
-                        ) => true,
-                        _ => false,
-                    };
-                    if add_resolution {
-                        acc.add_resolution(ctx, name, res);
+                    if scope_def_applicable(res) {
+                        if let ScopeDef::ModuleDef(hir::ModuleDef::Trait(trait_)) = res {
+                            if in_dyn && !is_object_safe(ctx, trait_) {
+                                cov_mark::hit!(skip_non_object_safe_trait);
+                                return;
+                            }
+                            if after_dyn_principal && !trait_.is_auto(ctx.db) {
+                                cov_mark::hit!(only_auto_traits_after_dyn_principal);
+                                return;
+                            }
+                            add_fn_trait_sugar(acc, ctx, &name, trait_);
+                        }
+                        add_resolution_transliterated(acc, ctx, name, res);
                     }
                 });
+                if allows_relaxed_bounds(ctx) {
+                    acc.add_keyword(ctx, "?Sized");
+                }
+                if after_dyn_principal {
+                    add_lifetimes(acc, ctx);
+                }
+                add_private_items_in_crate(acc, ctx, &scope_def_applicable);
                 return;
             }
             if let Some(ImmediateLocation::GenericArgList(arg_list)) = &ctx.completion_location {
+                match ctx.expected_generic_param(arg_list) {
+                    Some(hir::GenericParam::LifetimeParam(_)) => {
+                        cov_mark::hit!(complete_lifetime_in_generic_arg);
+                        add_lifetimes(acc, ctx);
+                    }
+                    Some(hir::GenericParam::ConstParam(_)) => {
+                        if let Some(cap) = ctx.config.snippet_cap {
+                            cov_mark::hit!(complete_inline_const_in_generic_arg);
+                            let mut item = CompletionItem::new(
+                                CompletionItemKind::Snippet,
+                                ctx.source_range(),
+                                "const { … }",
+                            );
+                            item.lookup_by("const").insert_snippet(cap, "const { $0 }");
+                            item.add_to(acc);
+                        }
+                    }
+                    _ => (),
+                }
                 if let Some(path_seg) = arg_list.syntax().parent().and_then(ast::PathSegment::cast)
                 {
                     if path_seg.syntax().ancestors().find_map(ast::TypeBound::cast).is_some() {
                         if let Some(hir::PathResolution::Def(hir::ModuleDef::Trait(trait_))) =
                             ctx.sema.resolve_path(&path_seg.parent_path())
                         {
+                            // Associated types can only be bound once, so skip the ones that
+                            // already are, besides the binding being completed.
+                            let bound: FxHashSet<_> = arg_list
+                                .generic_args()
+                                .filter_map(|arg| match arg {
+                                    ast::GenericArg::AssocTypeArg(it)
+                                        if !it
+                                            .syntax()
+                                            .text_range()
+                                            .contains_inclusive(ctx.position.offset) =>
+                                    {
+                                        Some(it.name_ref()?.text().to_string())
+                                    }
+                                    _ => None,
+                                })
+                                .collect();
+                            // A supertrait might be reachable through multiple paths, so
+                            // deduplicate its items.
+                            let mut seen = FxHashSet::default();
                             trait_.items_with_supertraits(ctx.sema.db).into_iter().for_each(|it| {
                                 if let hir::AssocItem::TypeAlias(alias) = it {
-                                    cov_mark::hit!(complete_assoc_type_in_generics_list);
-                                    acc.add_type_alias_with_eq(ctx, alias)
+                                    if bound.contains(alias.name(ctx.db).to_smol_str().as_str()) {
+                                        cov_mark::hit!(skip_bound_assoc_type_in_generics_list);
+                                        return;
+                                    }
+                                    if seen.insert(it) {
+                                        cov_mark::hit!(complete_assoc_type_in_generics_list);
+                                        acc.add_type_alias_with_eq(ctx, alias)
+                                    }
                                 }
                             });
                         }
                     }
                 }
             }
+            // Glob imports can bring the same item into scope under the same name multiple times,
+            // only list each name of it once.
+            let mut defs: Vec<(hir::Name, ScopeDef)> = Vec::new();
+            let mut seen = FxHashSet::default();
             ctx.process_all_names(&mut |name, def| {
-                if scope_def_applicable(def) {
-                    acc.add_resolution(ctx, name, def);
+                if scope_def_applicable(def) && seen.insert((name.clone(), def)) {
+                    defs.push((name, def));
                 }
             });
+            let ambiguous = ambiguous_glob_imports(ctx, &mut defs, &scope_def_applicable);
+            for (name, def) in defs {
+                if acc.is_saturated() {
+                    // Rendering is the expensive part, so skip it for items nobody asked for.
+                    cov_mark::hit!(type_path_stops_at_saturated_sink);
+                    return;
+                }
+                add_resolution_transliterated(acc, ctx, name, def);
+            }
+            for (name, def, path) in ambiguous {
+                cov_mark::hit!(complete_ambiguous_glob_import);
+                let mut item = render_resolution(RenderContext::new(ctx), name, def);
+                item.lookup_by(path.clone()).detail(path.clone()).insert_text(path);
+                acc.add(item.build());
+            }
+            add_private_items_in_crate(acc, ctx, &scope_def_applicable);
         }
     }
 }
 
-pub(crate) fn complete_inferred_type(acc: &mut Completions, ctx: &CompletionContext) -> Option<()> {
+/// Returns the type inferred for the type annotation being completed, rendered as source code
+/// that is valid in the current module.
+pub(crate) fn inferred_type_string(ctx: &CompletionContext) -> Option<String> {
+    inferred_type(ctx)?.display_source_code(ctx.db, ctx.module.into()).ok()
+}
+
+fn inferred_type(ctx: &CompletionContext) -> Option<hir::Type> {
     use TypeAnnotation::*;
-    let pat = match &ctx.completion_location {
-        Some(ImmediateLocation::TypeAnnotation(t)) => t,
-        _ => return None,
+    let ty = match ctx.completion_location.as_ref()? {
+        ImmediateLocation::TypeAnnotation(it) => match it {
+            Let(pat) | FnParam(pat) | ClosureParam(pat) => ctx.sema.type_of_pat(pat.as_ref()?),
+            Const(exp) | Static(exp) | RetType(exp) => ctx.sema.type_of_expr(exp.as_ref()?),
+            // The type of `self` is whatever is written down.
+            SelfParam(_) => None,
+        },
+        ImmediateLocation::GenericArgList(arg_list) => {
+            return inferred_generic_arg_type(ctx, arg_list)
+        }
+        _ => None,
+    }?;
+    Some(ty.adjusted())
+}
+
+/// Infers the type of the generic argument being completed in the type annotation of a let
+/// statement from its initializer, like `Foo` in `let x: Option<$0> = Some(Foo);`.
+fn inferred_generic_arg_type(
+    ctx: &CompletionContext,
+    arg_list: &ast::GenericArgList,
+) -> Option<hir::Type> {
+    let index = arg_list
+        .generic_args()
+        .filter(|arg| matches!(arg, ast::GenericArg::TypeArg(_)))
+        .take_while(|arg| arg.syntax().text_range().end() < ctx.position.offset)
+        .count();
+    let ty = generic_arg_owner_type(ctx, arg_list)?.type_arguments().nth(index)?;
+    if ty.is_unknown() {
+        return None;
+    }
+    cov_mark::hit!(complete_inferred_generic_arg_type);
+    Some(ty)
+}
+
+/// Returns the inferred type of the type whose generic arguments are given by `arg_list`, if that
+/// type is part of the type annotation of a let statement.
+fn generic_arg_owner_type(
+    ctx: &CompletionContext,
+    arg_list: &ast::GenericArgList,
+) -> Option<hir::Type> {
+    let path = ast::PathSegment::cast(arg_list.syntax().parent()?)?.parent_path();
+    let owner = ast::PathType::cast(path.syntax().parent()?)?;
+    let owner_parent = owner.syntax().parent()?;
+    let ty = match ast::LetStmt::cast(owner_parent.clone()) {
+        Some(let_stmt) => ctx.sema.type_of_expr(&let_stmt.initializer()?)?.original,
+        None => {
+            // Nested generic arguments, like `Vec<Option<$0>>`, are found in the inferred type of
+            // the enclosing generic argument.
+            let type_arg = ast::TypeArg::cast(owner_parent)?;
+            let outer_list = ast::GenericArgList::cast(type_arg.syntax().parent()?)?;
+            let index = outer_list
+                .generic_args()
+                .filter(|arg| matches!(arg, ast::GenericArg::TypeArg(_)))
+                .position(|arg| arg.syntax() == type_arg.syntax())?;
+            generic_arg_owner_type(ctx, &outer_list)?.type_arguments().nth(index)?
+        }
     };
-    let x = match pat {
-        Let(pat) | FnParam(pat) => ctx.sema.type_of_pat(pat.as_ref()?),
-        Const(exp) | RetType(exp) => ctx.sema.type_of_expr(exp.as_ref()?),
-    }?
-    .adjusted();
-    let ty_string = x.display_source_code(ctx.db, ctx.module.into()).ok()?;
-    acc.add(render_type_inference(ty_string, ctx));
+    // The arguments only line up if the annotation names the inferred type itself.
+    match ctx.sema.resolve_path(&path)? {
+        hir::PathResolution::Def(hir::ModuleDef::Adt(adt)) if ty.as_adt() == Some(adt) => Some(ty),
+        _ => None,
+    }
+}
+
+pub(crate) fn complete_inferred_type(acc: &mut Completions, ctx: &CompletionContext) -> Option<()> {
+    let x = inferred_type(ctx)?;
+    let is_ret_type = matches!(
+        ctx.completion_location,
+        Some(ImmediateLocation::TypeAnnotation(TypeAnnotation::RetType(_)))
+    );
+    if x.is_never() && is_ret_type {
+        // Already offered as a keyword.
+        return None;
+    }
+    let mut ty_string = inferred_type_string(ctx)?;
+
+    // If the type is an ADT that is not in scope, offer to import it instead of qualifying it.
+    let mut import = None;
+    if let Some(adt) = x.as_adt() {
+        let item = hir::ItemInNs::from(hir::ModuleDef::from(adt));
+        let import_path = ctx
+            .module
+            .find_use_path_prefixed(ctx.db, item, ctx.config.insert_use.prefix_kind)
+            .filter(|it| it.len() > 1);
+        if let Some(import_path) = import_path {
+            if let Some(it) = x.display_source_code_with_import(ctx.db, ctx.module, adt) {
+                ty_string = it;
+                import = Some(LocatedImport::new(import_path, item, item, None));
+            }
+        }
+    }
+    let is_let = matches!(
+        ctx.completion_location,
+        Some(ImmediateLocation::TypeAnnotation(TypeAnnotation::Let(_)))
+    );
+    if let (true, true, Some(cap)) =
+        (is_let, ctx.config.enable_smart_pointer_inferred_types, ctx.config.snippet_cap)
+    {
+        const WRAPPERS: [&str; 3] = ["Box", "Rc", "Arc"];
+        let mut wrappers_in_scope = FxHashSet::default();
+        ctx.process_all_names(&mut |name, def| {
+            if let ScopeDef::ModuleDef(hir::ModuleDef::Adt(_)) = def {
+                let name = name.to_smol_str();
+                if WRAPPERS.contains(&&*name) {
+                    wrappers_in_scope.insert(name);
+                }
+            }
+        });
+        for wrapper in WRAPPERS {
+            if wrappers_in_scope.contains(wrapper) {
+                acc.add(render_wrapped_type_inference(
+                    wrapper,
+                    &ty_string,
+                    import.clone(),
+                    cap,
+                    ctx,
+                ));
+            }
+        }
+    }
+    acc.add(render_type_inference(ty_string, import, ctx));
     None
 }
 
+/// Returns the part of the identifier under the cursor that has been typed before it.
+fn typed_prefix<'a>(ctx: &'a CompletionContext) -> &'a str {
+    let token = &ctx.original_token;
+    if token.kind() != SyntaxKind::IDENT {
+        return "";
+    }
+    let len = usize::from(ctx.position.offset - token.text_range().start());
+    token.text().get(..len).unwrap_or_default()
+}
+
+/// Checks whether all characters of `query` appear in `name` in order, ignoring case.
+fn fuzzy_matches(name: &str, query: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    query.chars().flat_map(char::to_lowercase).all(|q| name.any(|c| c == q))
+}
+
+/// Takes the names out of `defs` that multiple glob imports of the current module bring into scope
+/// for different items, returning these items together with the path to name each of them
+/// unambiguously.
+///
+/// Such names can't be used as is, but name resolution picks one of the items regardless.
+fn ambiguous_glob_imports(
+    ctx: &CompletionContext,
+    defs: &mut Vec<(hir::Name, ScopeDef)>,
+    scope_def_applicable: &dyn Fn(ScopeDef) -> bool,
+) -> Vec<(hir::Name, ScopeDef, String)> {
+    let mut res = Vec::new();
+    for (name, candidates) in ctx.module.ambiguous_glob_types(ctx.db) {
+        let is_candidate = |(it, def): &(hir::Name, ScopeDef)| {
+            *it == name
+                && candidates.iter().any(|&candidate| *def == ScopeDef::ModuleDef(candidate))
+        };
+        // Names declared in an inner scope shadow the glob imports.
+        if !defs.iter().any(is_candidate) {
+            continue;
+        }
+        defs.retain(|it| !is_candidate(it));
+        for candidate in candidates {
+            let def = ScopeDef::ModuleDef(candidate);
+            if !scope_def_applicable(def) {
+                continue;
+            }
+            let module_path = candidate
+                .module(ctx.db)
+                .and_then(|it| ctx.module.find_use_path(ctx.db, hir::ModuleDef::Module(it)));
+            if let Some(module_path) = module_path {
+                res.push((name.clone(), def, format!("{}::{}", module_path, name)));
+            }
+        }
+    }
+    res.sort_by(|(.., a), (.., b)| a.cmp(b));
+    res
+}
+
+/// Adds `def`, making snake_case modules reachable through the CamelCase spelling of their name
+/// if that is what has been typed so far, as in `MyMod` for `my_module`.
+fn add_resolution_transliterated(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+    name: hir::Name,
+    def: ScopeDef,
+) {
+    let lookup = match def {
+        ScopeDef::ModuleDef(hir::ModuleDef::Module(_)) => {
+            camel_case_module_lookup(&name.to_smol_str(), typed_prefix(ctx))
+        }
+        _ => None,
+    };
+    match lookup {
+        Some(lookup) if !ctx.is_scope_def_hidden(def) => {
+            cov_mark::hit!(complete_module_by_camel_case);
+            let mut item = render_resolution(RenderContext::new(ctx), name, def);
+            item.lookup_by(lookup);
+            acc.add(item.build());
+        }
+        _ => acc.add_resolution(ctx, name, def),
+    }
+}
+
+/// Returns the CamelCase spelling of the snake_case module `name` if `query` is a CamelCase
+/// prefix of it, ignoring case and underscores.
+fn camel_case_module_lookup(name: &str, query: &str) -> Option<String> {
+    if !query.chars().any(|c| c.is_uppercase()) || name.chars().any(|c| c.is_uppercase()) {
+        return None;
+    }
+    fn normalize(s: &str) -> impl Iterator<Item = char> + '_ {
+        s.chars().filter(|&c| c != '_').flat_map(char::to_lowercase)
+    }
+    let mut name_chars = normalize(name);
+    if !normalize(query).all(|q| name_chars.next() == Some(q)) {
+        return None;
+    }
+    let camel = name
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().into_iter().flat_map(char::to_uppercase).chain(chars)
+        })
+        .collect();
+    Some(camel)
+}
+
+/// Checks whether a `_` placeholder may be written in place of the type being completed. This is
+/// only the case within bodies, item signatures need to spell out their types.
+fn allows_inferred_type(ctx: &CompletionContext) -> bool {
+    match &ctx.completion_location {
+        Some(ImmediateLocation::TypeAnnotation(TypeAnnotation::Let(_))) => (),
+        Some(ImmediateLocation::GenericArgList(arg_list)) => {
+            if let Some(hir::GenericParam::LifetimeParam(_) | hir::GenericParam::ConstParam(_)) =
+                ctx.expected_generic_param(arg_list)
+            {
+                return false;
+            }
+        }
+        _ => return false,
+    }
+    ctx.token
+        .parent_ancestors()
+        .find_map(|node| {
+            if ast::Item::can_cast(node.kind()) {
+                Some(false)
+            } else if ast::LetStmt::can_cast(node.kind()) || ast::Expr::can_cast(node.kind()) {
+                Some(true)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(false)
+}
+
+/// Checks whether we are completing a bound that may be a `?Sized` relaxation, which is only
+/// allowed for the bounds of type parameters and associated types.
+fn allows_relaxed_bounds(ctx: &CompletionContext) -> bool {
+    let prev = match &ctx.previous_token {
+        Some(it) => it,
+        None => return false,
+    };
+    let owner = match prev.kind() {
+        T![:] => prev.parent(),
+        // The `+` is part of the bound list.
+        T![+] => prev.parent().and_then(|it| it.parent()),
+        _ => None,
+    };
+    owner.map_or(false, |it| match it.kind() {
+        SyntaxKind::TYPE_PARAM | SyntaxKind::TYPE_ALIAS => true,
+        // Lifetimes can't be unsized.
+        SyntaxKind::WHERE_PRED => {
+            ast::WherePred::cast(it).map_or(false, |it| it.lifetime().is_none())
+        }
+        _ => false,
+    })
+}
+
+/// Checks whether the definition comes from one of the crates excluded by the config.
+fn is_from_excluded_crate(ctx: &CompletionContext, def: hir::ModuleDef) -> bool {
+    let excluded = &ctx.config.excluded_crates;
+    if excluded.is_empty() {
+        return false;
+    }
+    // Crate roots have no parent module to look at.
+    let krate = match def {
+        hir::ModuleDef::Module(it) => Some(it.krate()),
+        _ => def.module(ctx.db).map(|it| it.krate()),
+    };
+    krate
+        .and_then(|it| it.display_name(ctx.db))
+        .map_or(false, |name| excluded.contains(name.crate_name()))
+}
+
+/// Adds the types of the current crate that aren't in scope but can be reached through a
+/// `crate::` path from here, like the `pub(crate)` types of sibling modules, together with an
+/// import of that path.
+fn add_private_items_in_crate(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+    scope_def_applicable: &dyn Fn(ScopeDef) -> bool,
+) {
+    // The import is only computed on resolve.
+    if !ctx.config.enable_private_items_in_crate || !ctx.config.enable_imports_on_the_fly {
+        return;
+    }
+    let mut in_scope = FxHashSet::default();
+    ctx.process_all_names_raw(&mut |_, res| {
+        if let ScopeDef::ModuleDef(def) = res {
+            in_scope.insert(def);
+        }
+    });
+    let candidates = items_locator::items_with_name(
+        &ctx.sema,
+        ctx.krate,
+        NameToImport::Fuzzy(typed_prefix(ctx).to_owned()),
+        AssocItemSearch::Exclude,
+        Some(items_locator::DEFAULT_QUERY_SEARCH_LIMIT.inner()),
+    );
+    for item in candidates {
+        let def = match item.as_module_def() {
+            Some(
+                def @ (hir::ModuleDef::Adt(_)
+                | hir::ModuleDef::Trait(_)
+                | hir::ModuleDef::TypeAlias(_)),
+            ) => def,
+            _ => continue,
+        };
+        if in_scope.contains(&def)
+            || !scope_def_applicable(ScopeDef::ModuleDef(def))
+            || !is_reachable_through_crate_path(ctx, def)
+        {
+            continue;
+        }
+        let import_path = match crate_relative_path(ctx.db, def) {
+            Some(it) => it,
+            None => continue,
+        };
+        let import = LocatedImport::new(import_path.clone(), item, item, Some(import_path));
+        if let Some(builder) = render_resolution_with_import(RenderContext::new(ctx), import) {
+            cov_mark::hit!(complete_private_item_in_crate);
+            acc.add(builder.build());
+        }
+    }
+}
+
+/// Checks whether a definition of the current crate, and every module on its `crate::` path, is
+/// visible from the current module, so that importing it compiles.
+fn is_reachable_through_crate_path(ctx: &CompletionContext, def: hir::ModuleDef) -> bool {
+    let module = match def.module(ctx.db) {
+        Some(it) if it.krate() == ctx.krate => it,
+        _ => return false,
+    };
+    let from = ctx.module.into();
+    def.visibility(ctx.db).is_visible_from(ctx.db, from)
+        && module
+            .path_to_root(ctx.db)
+            .into_iter()
+            .filter(|it| !it.is_crate_root(ctx.db))
+            .all(|it| it.visibility(ctx.db).is_visible_from(ctx.db, from))
+}
+
+/// Builds the `crate::` path of a definition.
+pub(crate) fn crate_relative_path(db: &RootDatabase, def: hir::ModuleDef) -> Option<ModPath> {
+    let module = def.module(db)?;
+    let mut segments: Vec<_> =
+        module.path_to_root(db).into_iter().rev().filter_map(|it| it.name(db)).collect();
+    segments.push(def.name(db)?);
+    Some(ModPath::from_segments(hir::PathKind::Crate, segments))
+}
+
+/// Checks whether we are completing right after the `&` of a reference type, where a lifetime
+/// may follow. Lifetimes come before the `mut` of a mutable reference, so `&mut $0` doesn't count.
+fn in_ref_type_lifetime_position(ctx: &CompletionContext) -> bool {
+    ctx.previous_token.as_ref().map_or(false, |prev| {
+        prev.kind() == T![&] && prev.parent().map_or(false, |it| it.kind() == SyntaxKind::REF_TYPE)
+    })
+}
+
+/// Adds the lifetimes in scope as well as `'static`.
+fn add_lifetimes(acc: &mut Completions, ctx: &CompletionContext) {
+    ctx.process_all_names_raw(&mut |name, res| {
+        if let ScopeDef::GenericParam(hir::GenericParam::LifetimeParam(_)) = res {
+            acc.add_lifetime(ctx, name);
+        }
+    });
+    acc.add_lifetime(ctx, hir::known::STATIC_LIFETIME);
+}
+
+/// Checks whether we are completing a bound of a trait object type, `dyn $0` or `dyn A + $0`.
+fn in_dyn_bound(ctx: &CompletionContext) -> bool {
+    let prev = match &ctx.previous_token {
+        Some(it) => it,
+        None => return false,
+    };
+    match prev.kind() {
+        T![dyn] => true,
+        T![+] => prev
+            .parent()
+            .and_then(|it| it.parent())
+            .map_or(false, |it| it.kind() == SyntaxKind::DYN_TRAIT_TYPE),
+        _ => false,
+    }
+}
+
+/// Checks whether we are completing an additional bound of a trait object after its principal
+/// trait, as in `dyn Trait + $0`, where only auto traits and lifetimes are allowed.
+fn after_dyn_principal_trait(ctx: &CompletionContext) -> bool {
+    let prev = match &ctx.previous_token {
+        Some(it) if it.kind() == T![+] => it,
+        _ => return false,
+    };
+    // The previous token lives in the file with the fake identifier, so look the bounds up in the
+    // original file for them to be resolvable.
+    let original_file = match ctx.original_token.parent_ancestors().last() {
+        Some(it) => it,
+        None => return false,
+    };
+    let bound_list = match original_file
+        .covering_element(prev.text_range())
+        .parent()
+        .and_then(ast::TypeBoundList::cast)
+    {
+        Some(it) => it,
+        None => return false,
+    };
+    bound_list
+        .bounds()
+        .take_while(|bound| bound.syntax().text_range().end() <= prev.text_range().start())
+        .filter_map(|bound| match bound.ty()? {
+            ast::Type::PathType(it) => match ctx.sema.resolve_path(&it.path()?)? {
+                hir::PathResolution::Def(hir::ModuleDef::Trait(it)) => Some(it),
+                _ => None,
+            },
+            _ => None,
+        })
+        .any(|trait_| !trait_.is_auto(ctx.db))
+}
+
+/// Approximates whether a trait can be made into an object, as hir doesn't model object safety
+/// yet. Traits with associated consts or generic methods are rejected, unless the method opts
+/// out of the trait object with a `where Self: Sized` clause.
+fn is_object_safe(ctx: &CompletionContext, trait_: hir::Trait) -> bool {
+    trait_.items_with_supertraits(ctx.db).into_iter().all(|item| match item {
+        hir::AssocItem::Const(_) => false,
+        hir::AssocItem::Function(func) => {
+            hir::GenericDef::Function(func).type_params(ctx.db).is_empty()
+                || requires_sized_self(ctx, func)
+        }
+        hir::AssocItem::TypeAlias(_) => true,
+    })
+}
+
+fn requires_sized_self(ctx: &CompletionContext, func: hir::Function) -> bool {
+    let sized = match ctx.famous_defs().core_marker_Sized() {
+        Some(it) => it,
+        None => return false,
+    };
+    let where_clause = match ctx.sema.source(func).and_then(|it| it.value.where_clause()) {
+        Some(it) => it,
+        None => return false,
+    };
+    let is_self = |ty: ast::Type| match ty {
+        ast::Type::PathType(it) => it
+            .path()
+            .and_then(|it| it.as_single_segment())
+            .map_or(false, |it| matches!(it.kind(), Some(ast::PathSegmentKind::SelfTypeKw))),
+        _ => false,
+    };
+    where_clause.predicates().any(|pred| {
+        pred.ty().map_or(false, is_self)
+            && pred.type_bound_list().map_or(false, |it| {
+                it.bounds().any(|bound| match bound.ty() {
+                    Some(ast::Type::PathType(it)) => {
+                        it.path().and_then(|it| ctx.sema.resolve_path(&it))
+                            == Some(hir::PathResolution::Def(hir::ModuleDef::Trait(sized)))
+                    }
+                    _ => false,
+                })
+            })
+    })
+}
+
+fn implemented_trait(ctx: &CompletionContext) -> Option<hir::Trait> {
+    let impl_def = ctx.impl_def.as_ref()?;
+    // With nothing typed after the `for` yet, the trait is the only type in the impl.
+    let trait_ = impl_def.trait_().or_else(|| impl_def.for_token().and(impl_def.self_ty()))?;
+    let path = match trait_ {
+        ast::Type::PathType(it) => it.path()?,
+        _ => return None,
+    };
+    match ctx.sema.resolve_path(&path)? {
+        hir::PathResolution::Def(hir::ModuleDef::Trait(it)) => Some(it),
+        _ => None,
+    }
+}
+
+/// Adds the parenthesized `Fn(..) -> ..` form of the closure traits as a snippet.
+fn add_fn_trait_sugar(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+    name: &hir::Name,
+    trait_: hir::Trait,
+) {
+    let cap = match ctx.config.snippet_cap {
+        Some(it) => it,
+        None => return,
+    };
+    let attrs = trait_.attrs(ctx.db);
+    if !matches!(
+        attrs.by_key("lang").string_value().map(|it| it.as_str()),
+        Some("fn" | "fn_mut" | "fn_once")
+    ) {
+        return;
+    }
+    cov_mark::hit!(complete_fn_trait_sugar);
+    let mut item = CompletionItem::new(
+        CompletionItemKind::Snippet,
+        ctx.source_range(),
+        format!("{}(..) -> ..", name),
+    );
+    item.insert_snippet(cap, format!("{}(${{1}}) -> ${{2}}", name));
+    item.add_to(acc);
+}
+
+/// Returns the traits an associated type of a trait is bounded by in its declaration, that is
+/// `Bound` in `type Assoc: Bound;`.
+fn assoc_type_bounds(ctx: &CompletionContext, alias: hir::TypeAlias) -> Vec<hir::Trait> {
+    let source = match ctx.sema.source(alias) {
+        Some(it) => it.value,
+        None => return Vec::new(),
+    };
+    source
+        .type_bound_list()
+        .into_iter()
+        .flat_map(|it| it.bounds())
+        .filter_map(|bound| match bound.ty()? {
+            ast::Type::PathType(it) => match ctx.sema.resolve_path(&it.path()?)? {
+                hir::PathResolution::Def(hir::ModuleDef::Trait(it)) => Some(it),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Adds an associated item that is applicable in type position, that is associated types and,
+/// when inside of a generic argument list, associated consts.
 fn add_assoc_item(acc: &mut Completions, ctx: &CompletionContext, item: hir::AssocItem) {
     match item {
-        hir::AssocItem::Const(ct) if ctx.expects_generic_arg() => acc.add_const(ctx, ct),
+        hir::AssocItem::Const(ct) if ctx.expects_generic_arg() => {
+            cov_mark::hit!(complete_assoc_const_in_generic_arg);
+            acc.add_const(ctx, ct)
+        }
         hir::AssocItem::Function(_) | hir::AssocItem::Const(_) => (),
         hir::AssocItem::TypeAlias(ty) => acc.add_type_alias(ctx, ty),
     }
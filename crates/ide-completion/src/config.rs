@@ -4,7 +4,7 @@
 //! module, and we use to statically check that we only produce snippet
 //! completions if we are allowed to.
 
-use ide_db::{imports::insert_use::InsertUseConfig, SnippetCap};
+use ide_db::{base_db::CrateName, imports::insert_use::InsertUseConfig, SnippetCap};
 
 use crate::snippet::Snippet;
 
@@ -14,10 +14,17 @@ pub struct CompletionConfig {
     pub enable_imports_on_the_fly: bool,
     pub enable_self_on_the_fly: bool,
     pub enable_private_editable: bool,
+    pub enable_private_items_in_crate: bool,
+    pub enable_smart_pointer_inferred_types: bool,
     pub callable: Option<CallableSnippets>,
     pub snippet_cap: Option<SnippetCap>,
     pub insert_use: InsertUseConfig,
     pub snippets: Vec<Snippet>,
+    /// Crates whose items should not be offered in type position.
+    pub excluded_crates: Vec<CrateName>,
+    /// Types the user referenced recently in the current file, most recent first, which are
+    /// ranked higher in type position. The client is responsible for keeping track of these.
+    pub recently_used_types: Vec<hir::ModuleDef>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
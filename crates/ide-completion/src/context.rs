@@ -4,7 +4,8 @@ use std::iter;
 
 use base_db::SourceDatabaseExt;
 use hir::{
-    HasAttrs, Local, Name, PathResolution, ScopeDef, Semantics, SemanticsScope, Type, TypeInfo,
+    HasAttrs, HasVisibility, Local, Name, PathResolution, ScopeDef, Semantics, SemanticsScope,
+    Type, TypeInfo,
 };
 use ide_db::{
     active_parameter::ActiveParameter,
@@ -14,7 +15,7 @@ use ide_db::{
 };
 use syntax::{
     algo::{find_node_at_offset, non_trivia_sibling},
-    ast::{self, AttrKind, HasArgList, HasName, NameOrNameRef},
+    ast::{self, AttrKind, HasArgList, HasGenericParams, HasName, NameOrNameRef},
     match_ast, AstNode, AstToken, Direction, NodeOrToken,
     SyntaxKind::{self, *},
     SyntaxNode, SyntaxToken, TextRange, TextSize, T,
@@ -23,8 +24,8 @@ use text_edit::Indel;
 
 use crate::{
     patterns::{
-        determine_location, is_in_loop_body, is_in_token_of_for_loop, previous_token,
-        ImmediateLocation,
+        determine_location, is_in_loop_body, is_in_macro_fragment_specifier,
+        is_in_token_of_for_loop, previous_token, ImmediateLocation,
     },
     CompletionConfig,
 };
@@ -53,6 +54,13 @@ pub(super) enum PathKind {
     },
     Type {
         in_tuple_struct: bool,
+        /// Whether this type is part of the trait or self type of an impl header
+        in_impl_header: bool,
+        /// Whether this type is the trait of an impl header, or its self type while there is no
+        /// `for` yet as in `impl $0`
+        is_impl_trait: bool,
+        /// Whether this type is the bounded type of a where predicate, in front of its `:`
+        in_where_pred: bool,
     },
     Attr {
         kind: AttrKind,
@@ -206,6 +214,8 @@ pub(super) struct NameRefContext {
     pub(super) keyword: Option<ast::Item>,
     /// The record expression this nameref is a field of
     pub(super) record_expr: Option<(ast::RecordExpr, bool)>,
+    /// The extern crate item this nameref is the crate name of
+    pub(super) extern_crate: Option<ast::ExternCrate>,
 }
 
 #[derive(Debug)]
@@ -221,6 +231,8 @@ pub(super) enum IdentContext {
     UnexpandedAttrTT {
         fake_attribute_under_caret: Option<ast::Attr>,
     },
+    /// The fragment specifier of a metavariable in a `macro_rules!` matcher, as in `$x:$0`
+    FragmentSpecifier,
 }
 
 #[derive(Debug)]
@@ -272,6 +284,15 @@ pub(crate) struct CompletionContext<'a> {
     pub(super) expected_name: Option<NameOrNameRef>,
     /// The expected type of what we are completing.
     pub(super) expected_type: Option<Type>,
+    /// The name of the type parameter the generic argument we are completing is given for.
+    pub(super) expected_generic_param_name: Option<Name>,
+    /// The default type of the type parameter the generic argument we are completing is given for.
+    pub(super) expected_generic_param_default: Option<Type>,
+    /// The trait bounds of the type parameter the generic argument we are completing is given for.
+    pub(super) expected_generic_param_bounds: Vec<hir::Trait>,
+    /// Whether the generic argument we are completing is given to `PhantomData`, which usually
+    /// refers to the generic parameters in scope.
+    pub(super) expects_phantom_data_arg: bool,
 
     /// The parent function of the cursor position if it exists.
     pub(super) function_def: Option<ast::Fn>,
@@ -291,6 +312,9 @@ pub(crate) struct CompletionContext<'a> {
     pub(super) existing_derives: FxHashSet<hir::Macro>,
 
     pub(super) locals: FxHashMap<Name, Local>,
+
+    /// The items of the prelude of the current crate.
+    pub(super) prelude_defs: FxHashSet<ScopeDef>,
 }
 
 impl<'a> CompletionContext<'a> {
@@ -357,6 +381,14 @@ impl<'a> CompletionContext<'a> {
         matches!(self.completion_location, Some(ImmediateLocation::GenericArgList(_)))
     }
 
+    /// Returns the lifetime whose bounds are being completed, as in `where 'a: $0`, where only
+    /// other lifetimes are valid.
+    pub(crate) fn bounded_lifetime(&self) -> Option<ast::Lifetime> {
+        // The bounds follow either the colon of the predicate or a `+` of its bound list.
+        let prev = self.previous_token.as_ref()?;
+        prev.parent_ancestors().take(2).find_map(ast::WherePred::cast)?.lifetime()
+    }
+
     pub(crate) fn path_context(&self) -> Option<&PathCompletionCtx> {
         self.nameref_ctx().and_then(|ctx| ctx.path_ctx.as_ref())
     }
@@ -373,6 +405,21 @@ impl<'a> CompletionContext<'a> {
         self.is_visible_impl(&item.visibility(self.db), &item.attrs(self.db), item.krate(self.db))
     }
 
+    /// Checks if a module is visible and not `doc(hidden)` at the completion site.
+    pub(crate) fn is_module_visible(&self, module: hir::Module) -> Visible {
+        let attrs = module.attrs(self.db);
+        // Crate roots have no visibility of their own, they are reachable through the extern
+        // prelude of the crates depending on them.
+        if module.is_crate_root(self.db) {
+            return if self.is_doc_hidden(&attrs, module.krate()) {
+                Visible::No
+            } else {
+                Visible::Yes
+            };
+        }
+        self.is_visible_impl(&module.visibility(self.db), &attrs, module.krate())
+    }
+
     pub(crate) fn is_scope_def_hidden(&self, scope_def: ScopeDef) -> bool {
         if let (Some(attrs), Some(krate)) = (scope_def.attrs(self.db), scope_def.krate(self.db)) {
             return self.is_doc_hidden(&attrs, krate);
@@ -479,7 +526,23 @@ impl<'a> CompletionContext<'a> {
 
         let original_token = original_file.syntax().token_at_offset(offset).left_biased()?;
         let token = sema.descend_into_macros_single(original_token.clone());
-        let scope = sema.scope_at_offset(&token.parent()?, offset)?;
+        // Trivia right after a (trailing) where clause belongs to the item itself, which resolves
+        // in the scope of the item's parent, so anchor the scope at the where clause instead to
+        // keep the item's generic parameters in scope. The same goes for the not yet typed
+        // aliased type of a type alias.
+        let scope_node = token
+            .prev_token()
+            .filter(|_| token.kind() == WHITESPACE)
+            .and_then(|it| it.parent_ancestors().find(|it| it.kind() == WHERE_CLAUSE))
+            .or_else(|| {
+                let alias = ast::TypeAlias::cast(token.parent()?)?;
+                if alias.eq_token()?.text_range().end() > offset {
+                    return None;
+                }
+                Some(alias.generic_param_list()?.syntax().clone())
+            })
+            .or_else(|| token.parent())?;
+        let scope = sema.scope_at_offset(&scope_node, offset)?;
         let krate = scope.krate();
         let module = scope.module();
 
@@ -489,6 +552,10 @@ impl<'a> CompletionContext<'a> {
                 locals.insert(name, local);
             }
         });
+        let prelude_defs = krate
+            .prelude(db)
+            .map(|prelude| prelude.scope(db, None).into_iter().map(|(_, def)| def).collect())
+            .unwrap_or_default();
 
         let mut ctx = CompletionContext {
             sema,
@@ -502,6 +569,10 @@ impl<'a> CompletionContext<'a> {
             module,
             expected_name: None,
             expected_type: None,
+            expected_generic_param_name: None,
+            expected_generic_param_default: None,
+            expected_generic_param_bounds: Vec::new(),
+            expects_phantom_data_arg: false,
             function_def: None,
             impl_def: None,
             incomplete_let: false,
@@ -513,6 +584,7 @@ impl<'a> CompletionContext<'a> {
             qualifier_ctx: Default::default(),
             existing_derives: Default::default(),
             locals,
+            prelude_defs,
         };
         ctx.expand_and_fill(
             original_file.syntax().clone(),
@@ -795,6 +867,22 @@ impl<'a> CompletionContext<'a> {
                             .map(|c| (Some(c.return_type()), None))
                             .unwrap_or((None, None))
                     },
+                    // [T; $0]
+                    ast::ArrayType(it) => {
+                        let after_semicolon = it
+                            .semicolon_token()
+                            .map_or(false, |it| it.text_range().end() <= self.position.offset);
+                        if !after_semicolon {
+                            return (None, None);
+                        }
+                        cov_mark::hit!(expected_type_array_len);
+                        (Some(hir::BuiltinType::usize().ty(self.db)), None)
+                    },
+                    // Foo<$0>
+                    ast::GenericArgList(it) => {
+                        let ty = self.expected_const_generic_arg_type(&it);
+                        (ty, None)
+                    },
                     ast::ParamList(_) => (None, None),
                     ast::Stmt(_) => (None, None),
                     ast::Item(_) => (None, None),
@@ -812,6 +900,71 @@ impl<'a> CompletionContext<'a> {
         }
     }
 
+    /// Calculate the type of the const parameter the generic argument at the cursor position is
+    /// being given for, if any.
+    fn expected_const_generic_arg_type(&self, arg_list: &ast::GenericArgList) -> Option<Type> {
+        match self.expected_generic_param(arg_list)? {
+            hir::GenericParam::ConstParam(it) => Some(it.ty(self.db)),
+            _ => None,
+        }
+    }
+
+    /// Calculate the generic parameter the generic argument at the cursor position is being given
+    /// for, if any.
+    pub(crate) fn expected_generic_param(
+        &self,
+        arg_list: &ast::GenericArgList,
+    ) -> Option<hir::GenericParam> {
+        let segment = arg_list.syntax().parent().and_then(ast::PathSegment::cast)?;
+        let generic_def: hir::GenericDef = match self.sema.resolve_path(&segment.parent_path())? {
+            PathResolution::Def(hir::ModuleDef::Adt(it)) => it.into(),
+            PathResolution::Def(hir::ModuleDef::TypeAlias(it)) => it.into(),
+            PathResolution::Def(hir::ModuleDef::Trait(it)) => it.into(),
+            PathResolution::Def(hir::ModuleDef::Function(it)) => it.into(),
+            _ => return None,
+        };
+        let offset = self.position.offset;
+        let (lifetime_args, other_args): (Vec<_>, Vec<_>) = arg_list
+            .generic_args()
+            .take_while(|arg| arg.syntax().text_range().end() < offset)
+            .partition(|arg| matches!(arg, ast::GenericArg::LifetimeArg(_)));
+        let (lifetime_params, other_params): (Vec<_>, Vec<_>) = generic_def
+            .params(self.db)
+            .into_iter()
+            .filter(|param| match param {
+                hir::GenericParam::TypeParam(it) => !it.is_implicit(self.db),
+                _ => true,
+            })
+            .partition(|param| matches!(param, hir::GenericParam::LifetimeParam(_)));
+        // Lifetime arguments have to come first, so we are looking at a lifetime position as long
+        // as no other kind of argument has been given yet.
+        if other_args.is_empty() && lifetime_args.len() < lifetime_params.len() {
+            return Some(lifetime_params[lifetime_args.len()]);
+        }
+        other_params.get(other_args.len()).copied()
+    }
+
+    /// Checks whether the type parameter is the `E` of `core::result::Result<T, E>`.
+    fn is_result_error_param(&self, param: hir::TypeParam) -> bool {
+        let result = match self.famous_defs().core_result_Result() {
+            Some(it) => hir::GenericDef::from(hir::Adt::from(it)),
+            None => return false,
+        };
+        param.merge().parent(self.db) == result
+            && result.type_params(self.db).get(1) == Some(&param.merge())
+    }
+
+    /// Resolves shorthand projections like `T::Assoc` that name an associated type of a type
+    /// parameter or `Self` without spelling out its trait, which path resolution gives up on when
+    /// the path is a qualifier.
+    pub(crate) fn resolve_assoc_type_shorthand(&self, path: &ast::Path) -> Option<hir::TypeAlias> {
+        let name = path.segment()?.name_ref()?;
+        let base = self.sema.resolve_path(&path.qualifier()?)?;
+        self.scope.assoc_type_shorthand_candidates(&base, |assoc_name, alias| {
+            (assoc_name.to_smol_str() == name.text().as_str()).then(|| alias)
+        })
+    }
+
     /// Fill the completion context, this is what does semantic reasoning about the surrounding context
     /// of the completion location.
     fn fill(
@@ -874,6 +1027,8 @@ impl<'a> CompletionContext<'a> {
                         original,
                         expanded: ast::String::cast(self.token.clone()),
                     };
+                } else if is_in_macro_fragment_specifier(syntax_element.clone()) {
+                    self.ident_ctx = IdentContext::FragmentSpecifier;
                 } else {
                     // Fix up trailing whitespace problem
                     // #[attr(foo = $0
@@ -900,6 +1055,30 @@ impl<'a> CompletionContext<'a> {
         };
         self.completion_location =
             determine_location(&self.sema, original_file, offset, &name_like);
+        if let Some(ImmediateLocation::GenericArgList(arg_list)) = &self.completion_location {
+            if let Some(hir::GenericParam::TypeParam(param)) = self.expected_generic_param(arg_list)
+            {
+                self.expected_generic_param_name = Some(param.name(self.db));
+                self.expected_generic_param_default =
+                    param.default(self.db).filter(|it| !it.is_unknown());
+                self.expected_generic_param_bounds = param.trait_bounds(self.db);
+                // The error type of a `Result` is usually a `std::error::Error`.
+                if self.is_result_error_param(param) {
+                    self.expected_generic_param_bounds.extend(self.famous_defs().std_error_Error());
+                }
+                self.expects_phantom_data_arg = self
+                    .famous_defs()
+                    .core_marker_PhantomData()
+                    .map_or(false, |it| param.merge().parent(self.db) == hir::GenericDef::from(it));
+            }
+        }
+        if let Some(ImmediateLocation::WherePredEqRhs(ast::Type::PathType(lhs))) =
+            &self.completion_location
+        {
+            if let Some(alias) = lhs.path().and_then(|it| self.resolve_assoc_type_shorthand(&it)) {
+                self.expected_generic_param_bounds = alias.trait_bounds(self.db);
+            }
+        }
         self.impl_def = self
             .sema
             .token_ancestors_with_macros(self.token.clone())
@@ -1033,6 +1212,7 @@ impl<'a> CompletionContext<'a> {
                 nameref,
                 record_expr: None,
                 keyword: None,
+                extern_crate: None,
             },
             None,
             QualifierCtx::default(),
@@ -1082,6 +1262,11 @@ impl<'a> CompletionContext<'a> {
                     });
                     return res;
                 },
+                ast::ExternCrate(extern_crate) => {
+                    nameref_ctx.extern_crate =
+                        find_node_in_file_compensated(original_file, &extern_crate);
+                    return res;
+                },
                 ast::MethodCallExpr(method) => {
                     let receiver = find_in_original_file(method.receiver(), original_file);
                     nameref_ctx.dot_access = Some(DotAccess {
@@ -1119,6 +1304,27 @@ impl<'a> CompletionContext<'a> {
                     find_node_in_file_compensated(original_file, &record_expr).zip(Some(true));
             }
         };
+        let in_impl_header = |node: &SyntaxNode| {
+            node.ancestors().find_map(ast::Impl::cast).map_or(false, |impl_| {
+                let range = node.text_range();
+                let contains = |ty: Option<ast::Type>| {
+                    ty.map_or(false, |ty| ty.syntax().text_range().contains_range(range))
+                };
+                contains(impl_.trait_()) || contains(impl_.self_ty())
+            })
+        };
+        let is_impl_trait = |node: &SyntaxNode| {
+            node.parent().and_then(ast::Impl::cast).map_or(false, |impl_| {
+                let is = |ty: Option<ast::Type>| ty.map_or(false, |ty| ty.syntax() == node);
+                is(impl_.trait_()) || (impl_.for_token().is_none() && is(impl_.self_ty()))
+            })
+        };
+        let is_where_pred_subject = |node: &SyntaxNode| {
+            node.parent()
+                .and_then(ast::WherePred::cast)
+                .and_then(|pred| pred.ty())
+                .map_or(false, |ty| ty.syntax() == node)
+        };
         let after_if_expr = |node: SyntaxNode| {
             let prev_expr = (|| {
                 let prev_sibling = non_trivia_sibling(node.into(), Direction::Prev)?.into_node()?;
@@ -1166,7 +1372,10 @@ impl<'a> CompletionContext<'a> {
             match_ast! {
                 match it {
                     ast::PathType(it) => Some(PathKind::Type {
-                        in_tuple_struct: it.syntax().parent().map_or(false, |it| ast::TupleField::can_cast(it.kind()))
+                        in_tuple_struct: it.syntax().parent().map_or(false, |it| ast::TupleField::can_cast(it.kind())),
+                        in_impl_header: in_impl_header(it.syntax()),
+                        is_impl_trait: is_impl_trait(it.syntax()),
+                        in_where_pred: is_where_pred_subject(it.syntax()),
                     }),
                     ast::PathExpr(it) => {
                         if let Some(p) = it.syntax().parent() {
@@ -1213,7 +1422,7 @@ impl<'a> CompletionContext<'a> {
                         let parent = it.syntax().parent();
                         match parent.as_ref().map(|it| it.kind()) {
                             Some(SyntaxKind::MACRO_PAT) => Some(PathKind::Pat),
-                            Some(SyntaxKind::MACRO_TYPE) => Some(PathKind::Type { in_tuple_struct: false }),
+                            Some(SyntaxKind::MACRO_TYPE) => Some(PathKind::Type { in_tuple_struct: false, in_impl_header: false, is_impl_trait: false, in_where_pred: false }),
                             Some(SyntaxKind::ITEM_LIST) => Some(PathKind::Item { kind: ItemListKind::Module }),
                             Some(SyntaxKind::ASSOC_ITEM_LIST) => Some(PathKind::Item { kind: match parent.and_then(|it| it.parent()) {
                                 Some(it) => match_ast! {
@@ -1785,6 +1994,24 @@ fn foo() {
         )
     }
 
+    #[test]
+    fn expected_type_const_generic_arg() {
+        check_expected_type_and_name(
+            r#"
+struct Foo<'lt, T, const N: usize>(&'lt T);
+fn foo() -> Foo<'static, u8, $0> {}
+"#,
+            expect![[r#"ty: usize, name: ?"#]],
+        );
+        check_expected_type_and_name(
+            r#"
+struct Foo<'lt, T, const N: usize>(&'lt T);
+fn foo() -> Foo<'static, $0> {}
+"#,
+            expect![[r#"ty: ?, name: ?"#]],
+        );
+    }
+
     #[test]
     fn expected_type_fn_ret_without_leading_char() {
         cov_mark::check!(expected_type_fn_ret_without_leading_char);
@@ -1904,4 +2131,15 @@ fn foo($0: Foo) {}
             expect![[r#"ty: ?, name: ?"#]],
         );
     }
+
+    #[test]
+    fn expected_type_array_len() {
+        cov_mark::check!(expected_type_array_len);
+        check_expected_type_and_name(
+            r#"
+fn foo<const N: usize>() -> [u8; $0] {}
+"#,
+            expect![[r#"ty: usize, name: ?"#]],
+        );
+    }
 }
@@ -147,9 +147,35 @@ pub struct CompletionRelevance {
     pub is_op_method: bool,
     /// Set for item completions that are private but in the workspace.
     pub is_private_editable: bool,
+    /// Set for items of the prelude completed in type position, which are in scope everywhere and
+    /// are therefore less likely to be looked for than local types.
+    pub is_prelude: bool,
+    /// Set for items marked as `#[deprecated]`.
+    pub is_deprecated: bool,
+    /// Set for types that are among the `recently_used_types` of the completion config.
+    pub is_recently_used: bool,
+    /// Set for completions fitting a position that usually makes use of the generic parameters of
+    /// the surrounding item, like the subject of a where predicate or the argument of
+    /// `PhantomData`.
+    pub fits_generic_params_use: bool,
+    /// Set for types given for a generic parameter that satisfy its bounds, like `String` in
+    /// `HashSet<$0>` where the parameter is bounded by `Hash`.
+    pub satisfies_generic_param_bounds: bool,
+    /// Set for types given for a generic parameter that are its default, like `S` in `Foo<$0>`
+    /// for `struct Foo<T = S>`.
+    pub is_generic_param_default: bool,
+    /// Set for type aliases and associated types given for a generic parameter with a name they
+    /// start with, like an associated type `Value` in `HashMap<K, $0>` for the parameter `V`.
+    pub is_generic_param_name_match: bool,
+    /// The number of generic parameters without defaults that a type completed in type position
+    /// has to be given, as in `HashMap<K, V>`.
+    pub required_generic_params: usize,
+    /// Set for snippets of plain type syntax, like tuples and arrays, which shouldn't crowd the
+    /// names in scope.
+    pub is_syntax_snippet: bool,
     /// Set for postfix snippet item completions
     pub postfix_match: Option<CompletionRelevancePostfixMatch>,
-    /// This is set for type inference results
+    /// This is set for type inference results matching what has been typed so far
     pub is_definite: bool,
 }
 
@@ -203,7 +229,7 @@ impl CompletionRelevance {
     /// See is_relevant if you need to make some judgement about score
     /// in an absolute sense.
     pub fn score(self) -> u32 {
-        let mut score = 0;
+        let mut score: u32 = 0;
         let CompletionRelevance {
             exact_name_match,
             type_match,
@@ -213,6 +239,15 @@ impl CompletionRelevance {
             requires_import,
             is_op_method,
             is_private_editable,
+            is_prelude,
+            is_deprecated,
+            is_recently_used,
+            fits_generic_params_use,
+            satisfies_generic_param_bounds,
+            is_generic_param_default,
+            is_generic_param_name_match,
+            required_generic_params,
+            is_syntax_snippet,
             postfix_match,
             is_definite,
         } = self;
@@ -221,6 +256,14 @@ impl CompletionRelevance {
         if !is_private_editable {
             score += 1;
         }
+        // lower rank items from the prelude
+        if !is_prelude {
+            score += 1;
+        }
+        // lower rank deprecated items
+        if !is_deprecated {
+            score += 1;
+        }
         // lower rank trait op methods
         if !is_op_method {
             score += 10;
@@ -236,6 +279,12 @@ impl CompletionRelevance {
         if exact_name_match {
             score += 10;
         }
+        if satisfies_generic_param_bounds {
+            score += 3;
+        }
+        if is_generic_param_default {
+            score += 8;
+        }
         score += match postfix_match {
             Some(CompletionRelevancePostfixMatch::Exact) => 100,
             Some(CompletionRelevancePostfixMatch::NonExact) => 0,
@@ -250,12 +299,28 @@ impl CompletionRelevance {
         if is_local {
             score += 1;
         }
+        if is_recently_used {
+            score += 1;
+        }
+        // slightly prefer types named like the generic parameter they are given for
+        if is_generic_param_name_match {
+            score += 2;
+        }
+        if fits_generic_params_use {
+            score += 1;
+        }
+        // lower rank snippets of plain syntax
+        if !is_syntax_snippet {
+            score += 1;
+        }
         if is_item_from_trait {
             score += 1;
         }
         if is_definite {
             score += 10;
         }
+        // lower rank types the more generic arguments they need, up to a bound
+        score = score.saturating_sub(required_generic_params.min(3) as u32);
         score
     }
 
@@ -271,6 +336,7 @@ impl CompletionRelevance {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum CompletionItemKind {
     SymbolKind(SymbolKind),
+    AssocType,
     Binding,
     BuiltinType,
     InferredType,
@@ -307,12 +373,14 @@ impl CompletionItemKind {
                 SymbolKind::Struct => "st",
                 SymbolKind::ToolModule => "tm",
                 SymbolKind::Trait => "tt",
+                SymbolKind::TraitAlias => "tr",
                 SymbolKind::TypeAlias => "ta",
                 SymbolKind::TypeParam => "tp",
                 SymbolKind::Union => "un",
                 SymbolKind::ValueParam => "vp",
                 SymbolKind::Variant => "ev",
             },
+            CompletionItemKind::AssocType => "as",
             CompletionItemKind::Binding => "bn",
             CompletionItemKind::BuiltinType => "bt",
             CompletionItemKind::InferredType => "it",
@@ -608,12 +676,35 @@ mod tests {
             vec![],
             vec![Cr { is_op_method: true, is_private_editable: true, ..default }],
             vec![Cr { is_op_method: true, ..default }],
-            vec![Cr { postfix_match: Some(CompletionRelevancePostfixMatch::NonExact), ..default }],
-            vec![Cr { is_private_editable: true, ..default }],
+            vec![
+                Cr { postfix_match: Some(CompletionRelevancePostfixMatch::NonExact), ..default },
+                Cr { required_generic_params: 3, ..default },
+                Cr { required_generic_params: 5, ..default },
+            ],
+            vec![Cr { required_generic_params: 2, ..default }],
+            vec![
+                Cr { is_private_editable: true, ..default },
+                Cr { is_prelude: true, ..default },
+                Cr { is_deprecated: true, ..default },
+                Cr { required_generic_params: 1, ..default },
+                Cr { is_syntax_snippet: true, ..default },
+            ],
             vec![default],
-            vec![Cr { is_local: true, ..default }],
-            vec![Cr { type_match: Some(CompletionRelevanceTypeMatch::CouldUnify), ..default }],
-            vec![Cr { type_match: Some(CompletionRelevanceTypeMatch::Exact), ..default }],
+            vec![
+                Cr { is_local: true, ..default },
+                Cr { is_recently_used: true, ..default },
+                Cr { fits_generic_params_use: true, ..default },
+            ],
+            vec![Cr { is_generic_param_name_match: true, ..default }],
+            vec![
+                Cr { type_match: Some(CompletionRelevanceTypeMatch::CouldUnify), ..default },
+                Cr { satisfies_generic_param_bounds: true, ..default },
+            ],
+            vec![Cr { exact_name_match: true, required_generic_params: 3, ..default }],
+            vec![
+                Cr { type_match: Some(CompletionRelevanceTypeMatch::Exact), ..default },
+                Cr { is_generic_param_default: true, ..default },
+            ],
             vec![Cr { exact_name_match: true, ..default }],
             vec![Cr { exact_name_match: true, is_local: true, ..default }],
             vec![Cr {
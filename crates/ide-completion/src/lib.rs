@@ -27,6 +27,7 @@ use text_edit::TextEdit;
 use crate::{completions::Completions, context::CompletionContext};
 
 pub use crate::{
+    completions::CompletionSink,
     config::{CallableSnippets, CompletionConfig},
     item::{
         CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevancePostfixMatch,
@@ -144,7 +145,7 @@ pub fn completions(
     config: &CompletionConfig,
     position: FilePosition,
     trigger_character: Option<char>,
-) -> Option<Completions> {
+) -> Option<Completions<'static>> {
     let ctx = &CompletionContext::new(db, position, config)?;
     let mut acc = Completions::default();
 
@@ -158,10 +159,12 @@ pub fn completions(
             completions::dot::complete_dot(acc, ctx);
             completions::expr::complete_expr_path(acc, ctx);
             completions::extern_abi::complete_extern_abi(acc, ctx);
+            completions::extern_crate::complete_extern_crate(acc, ctx);
             completions::field::complete_field_list(acc, ctx);
             completions::flyimport::import_on_the_fly(acc, ctx);
             completions::fn_param::complete_fn_param(acc, ctx);
             completions::format_string::format_string(acc, ctx);
+            completions::fragment_specifier::complete_fragment_specifier(acc, ctx);
             completions::item_list::complete_item_list(acc, ctx);
             completions::keyword::complete_expr_keyword(acc, ctx);
             completions::lifetime::complete_label(acc, ctx);
@@ -172,6 +175,7 @@ pub fn completions(
             completions::record::complete_record(acc, ctx);
             completions::snippet::complete_expr_snippet(acc, ctx);
             completions::snippet::complete_item_snippet(acc, ctx);
+            completions::snippet::complete_type_snippet(acc, ctx);
             completions::r#type::complete_type_path(acc, ctx);
             completions::r#type::complete_inferred_type(acc, ctx);
             completions::use_::complete_use_tree(acc, ctx);
@@ -182,6 +186,41 @@ pub fn completions(
     Some(acc)
 }
 
+/// Computes only the completions that are applicable in type position, that is the names,
+/// snippets and inferred types offered when completing a type.
+///
+/// Returns no completions if `position` isn't in type position.
+pub fn completions_in_type_position(
+    db: &RootDatabase,
+    config: &CompletionConfig,
+    position: FilePosition,
+) -> Vec<CompletionItem> {
+    let _p = profile::span("completions_in_type_position");
+    let mut items = Vec::new();
+    completions_in_type_position_with_sink(db, config, position, &mut items);
+    items
+}
+
+/// Like [`completions_in_type_position`], but hands each item to `sink` as soon as it has been
+/// produced instead of collecting all of them first.
+///
+/// Candidates stop being computed once the sink is saturated.
+pub fn completions_in_type_position_with_sink(
+    db: &RootDatabase,
+    config: &CompletionConfig,
+    position: FilePosition,
+    sink: &mut dyn CompletionSink,
+) {
+    let ctx = match CompletionContext::new(db, position, config) {
+        Some(it) => it,
+        None => return,
+    };
+    let mut acc = Completions::with_sink(sink);
+    completions::snippet::complete_type_snippet(&mut acc, &ctx);
+    completions::r#type::complete_type_path(&mut acc, &ctx);
+    completions::r#type::complete_inferred_type(&mut acc, &ctx);
+}
+
 /// Resolves additional completion data at the position given.
 /// This is used for import insertion done via completions like flyimport and custom user snippets.
 pub fn resolve_completion_edits(
@@ -209,8 +248,22 @@ pub fn resolve_completion_edits(
             Some(items_locator::DEFAULT_QUERY_SEARCH_LIMIT.inner()),
         );
         let import = items_with_name
-            .filter_map(|candidate| {
-                current_module.find_use_path_prefixed(db, candidate, config.insert_use.prefix_kind)
+            .flat_map(|candidate| {
+                let use_path = current_module.find_use_path_prefixed(
+                    db,
+                    candidate,
+                    config.insert_use.prefix_kind,
+                );
+                // Types of the current crate may also be imported through their `crate::` path
+                // if requested.
+                let crate_path = candidate
+                    .as_module_def()
+                    .filter(|def| {
+                        config.enable_private_items_in_crate
+                            && def.module(db).map(|it| it.krate()) == Some(current_crate)
+                    })
+                    .and_then(|def| completions::r#type::crate_relative_path(db, def));
+                use_path.into_iter().chain(crate_path)
             })
             .find(|mod_path| mod_path.to_string() == full_import_path);
         if let Some(import_path) = import {
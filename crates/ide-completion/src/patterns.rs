@@ -10,7 +10,7 @@ use syntax::{
     ast::{self, HasLoopBody, HasName},
     match_ast, AstNode, SyntaxElement,
     SyntaxKind::*,
-    SyntaxNode, SyntaxToken, TextRange, TextSize,
+    SyntaxNode, SyntaxToken, TextRange, TextSize, T,
 };
 
 #[cfg(test)]
@@ -20,8 +20,11 @@ use crate::tests::check_pattern_is_applicable;
 pub(crate) enum TypeAnnotation {
     Let(Option<ast::Pat>),
     FnParam(Option<ast::Pat>),
+    ClosureParam(Option<ast::Pat>),
     RetType(Option<ast::Expr>),
     Const(Option<ast::Expr>),
+    Static(Option<ast::Expr>),
+    SelfParam(ast::SelfParam),
 }
 
 /// Direct parent "thing" of what we are currently completing.
@@ -36,6 +39,16 @@ pub(crate) enum ImmediateLocation {
     // Only set from a type arg
     /// Original file ast node
     GenericArgList(ast::GenericArgList),
+    /// The aliased type of a type alias
+    /// Original file ast node
+    TypeAliasRhs(ast::TypeAlias),
+    /// The right hand side of an associated type equality predicate, as in `where T::Item = $0`,
+    /// holding its left hand side
+    /// Original file ast node
+    WherePredEqRhs(ast::Type),
+    /// The length of an array type, as in `[T; $0]`
+    /// Original file ast node
+    ArrayLen(ast::ArrayType),
 }
 
 pub(crate) fn determine_location(
@@ -92,6 +105,14 @@ pub(crate) fn determine_location(
                 let original = ast::Const::cast(name.syntax().parent()?)?;
                 ImmediateLocation::TypeAnnotation(TypeAnnotation::Const(original.body()))
             },
+            ast::Static(it) => {
+                if !it.ty().map_or(false, |x| x.syntax().text_range().contains(offset)) {
+                    return None;
+                }
+                let name = find_in_original_file(it.name(), original_file)?;
+                let original = ast::Static::cast(name.syntax().parent()?)?;
+                ImmediateLocation::TypeAnnotation(TypeAnnotation::Static(original.body()))
+            },
             ast::RetType(it) => {
                 if it.thin_arrow_token().is_none() {
                     return None;
@@ -123,7 +144,26 @@ pub(crate) fn determine_location(
                 if !it.ty().map_or(false, |x| x.syntax().text_range().contains(offset)) {
                     return None;
                 }
-                ImmediateLocation::TypeAnnotation(TypeAnnotation::FnParam(find_in_original_file(it.pat(), original_file)))
+                let pat = find_in_original_file(it.pat(), original_file);
+                let is_closure_param = it
+                    .syntax()
+                    .parent()
+                    .and_then(|it| it.parent())
+                    .map_or(false, |it| ast::ClosureExpr::can_cast(it.kind()));
+                ImmediateLocation::TypeAnnotation(if is_closure_param {
+                    TypeAnnotation::ClosureParam(pat)
+                } else {
+                    TypeAnnotation::FnParam(pat)
+                })
+            },
+            ast::SelfParam(it) => {
+                if it.colon_token().is_none() {
+                    return None;
+                }
+                if !it.ty().map_or(false, |x| x.syntax().text_range().contains(offset)) {
+                    return None;
+                }
+                ImmediateLocation::TypeAnnotation(TypeAnnotation::SelfParam(it))
             },
             ast::LetStmt(it) => {
                 if it.colon_token().is_none() {
@@ -134,6 +174,28 @@ pub(crate) fn determine_location(
                 }
                 ImmediateLocation::TypeAnnotation(TypeAnnotation::Let(find_in_original_file(it.pat(), original_file)))
             },
+            ast::TypeAlias(it) => {
+                if !it.ty().map_or(false, |x| x.syntax().text_range().contains(offset)) {
+                    return None;
+                }
+                let name = find_in_original_file(it.name(), original_file)?;
+                ImmediateLocation::TypeAliasRhs(ast::TypeAlias::cast(name.syntax().parent()?)?)
+            },
+            ast::ArrayType(it) => {
+                if it.semicolon_token().map_or(true, |it| it.text_range().end() > offset) {
+                    return None;
+                }
+                // The length being typed is missing from the original file.
+                let range = TextRange::new(it.syntax().text_range().start(), offset);
+                ImmediateLocation::ArrayLen(find_node_with_range(original_file, range)?)
+            },
+            ast::WherePred(it) => {
+                let eq = it.syntax().children_with_tokens().find(|it| it.kind() == T![=])?;
+                if eq.text_range().end() > offset {
+                    return None;
+                }
+                ImmediateLocation::WherePredEqRhs(find_in_original_file(it.ty(), original_file)?)
+            },
             _ => return None,
         }
     };
@@ -206,6 +268,39 @@ fn test_for_is_prev2() {
     check_pattern_is_applicable(r"fn __() { for i i$0 }", is_in_token_of_for_loop);
 }
 
+/// Checks whether the element is the fragment specifier of a metavariable in the matcher of a
+/// `macro_rules!` rule, as in `($x:$0) => {}`.
+pub(crate) fn is_in_macro_fragment_specifier(element: SyntaxElement) -> bool {
+    (|| {
+        let token = element.into_token()?;
+        let colon = previous_non_trivia_token(token.clone()).filter(|it| it.kind() == T![:])?;
+        let name = previous_non_trivia_token(colon)
+            .filter(|it| it.kind() == IDENT || it.kind().is_keyword())?;
+        let dollar = previous_non_trivia_token(name).filter(|it| it.kind() == T![$])?;
+        if dollar.parent() != token.parent() {
+            return None;
+        }
+        let rules = token.parent_ancestors().find_map(ast::MacroRules::cast)?.token_tree()?;
+        // The matcher is the token tree of the rule that is followed by its `=>`.
+        let matcher = token.parent_ancestors().take_while(|it| it != rules.syntax()).last()?;
+        let next = next_non_trivia_sibling(matcher.into())?;
+        Some(matches!(next.kind(), T![=] | T![=>]))
+    })()
+    .unwrap_or(false)
+}
+
+#[test]
+fn test_in_macro_fragment_specifier() {
+    check_pattern_is_applicable(
+        r"macro_rules! m { ($x:t$0) => {}; }",
+        is_in_macro_fragment_specifier,
+    );
+    check_pattern_is_applicable(
+        r"macro_rules! m { ($($x:t$0),*) => {}; }",
+        is_in_macro_fragment_specifier,
+    );
+}
+
 pub(crate) fn is_in_loop_body(node: &SyntaxNode) -> bool {
     node.ancestors()
         .take_while(|it| it.kind() != FN && it.kind() != CLOSURE_EXPR)
@@ -12,13 +12,18 @@ pub(crate) mod literal;
 
 use hir::{AsAssocItem, HasAttrs, HirDisplay, ScopeDef};
 use ide_db::{
-    helpers::item_name, imports::import_assets::LocatedImport, RootDatabase, SnippetCap, SymbolKind,
+    base_db::Edition, helpers::item_name, imports::import_assets::LocatedImport, RootDatabase,
+    SnippetCap, SymbolKind,
+};
+use syntax::{
+    ast::{self, HasGenericParams},
+    SmolStr, SyntaxKind, TextRange,
 };
-use syntax::{SmolStr, SyntaxKind, TextRange};
 
 use crate::{
     context::{PathCompletionCtx, PathKind},
     item::{Builder, CompletionRelevanceTypeMatch},
+    patterns::ImmediateLocation,
     render::{function::render_fn, literal::render_variant_lit, macro_::render_macro},
     CompletionContext, CompletionItem, CompletionItemKind, CompletionRelevance,
 };
@@ -130,9 +135,8 @@ pub(crate) fn render_field(
         .set_documentation(field.docs(ctx.db()))
         .set_deprecated(is_deprecated)
         .lookup_by(name.clone());
-    let is_keyword = SyntaxKind::from_keyword(name.as_str()).is_some();
-    if is_keyword && !matches!(name.as_str(), "self" | "crate" | "super" | "Self") {
-        item.insert_text(format!("r#{}", name));
+    if let Some(escaped_name) = escape_raw_ident(ctx.completion, &name) {
+        item.insert_text(escaped_name);
     }
     if let Some(_ref_match) = compute_ref_match(ctx.completion, ty) {
         // FIXME
@@ -187,10 +191,47 @@ pub(crate) fn render_resolution_with_import(
     Some(render_resolution_(ctx, local_name, Some(import_edit), resolution))
 }
 
-pub(crate) fn render_type_inference(ty_string: String, ctx: &CompletionContext) -> CompletionItem {
+pub(crate) fn render_type_inference(
+    ty_string: String,
+    import_to_add: Option<LocatedImport>,
+    ctx: &CompletionContext,
+) -> CompletionItem {
+    // Only rank the inferred type above everything else if it matches what has been typed so far.
+    let is_definite = ctx.original_token.kind() != SyntaxKind::IDENT
+        || ty_string.starts_with(ctx.original_token.text());
     let mut builder =
         CompletionItem::new(CompletionItemKind::InferredType, ctx.source_range(), ty_string);
-    builder.set_relevance(CompletionRelevance { is_definite: true, ..Default::default() });
+    builder.set_relevance(CompletionRelevance {
+        is_definite,
+        requires_import: import_to_add.is_some(),
+        ..Default::default()
+    });
+    if let Some(import_to_add) = import_to_add {
+        builder.add_import(import_to_add);
+    }
+    builder.build()
+}
+
+/// Renders the inferred type wrapped in a smart pointer, with the inner type as a placeholder.
+pub(crate) fn render_wrapped_type_inference(
+    wrapper: &str,
+    ty_string: &str,
+    import_to_add: Option<LocatedImport>,
+    cap: SnippetCap,
+    ctx: &CompletionContext,
+) -> CompletionItem {
+    let label = format!("{}<{}>", wrapper, ty_string);
+    let mut builder =
+        CompletionItem::new(CompletionItemKind::InferredType, ctx.source_range(), label);
+    builder.insert_snippet(cap, format!("{}<${{0:{}}}>", wrapper, ty_string));
+    // Never mark these as definite so that they rank below the bare inferred type.
+    builder.set_relevance(CompletionRelevance {
+        requires_import: import_to_add.is_some(),
+        ..Default::default()
+    });
+    if let Some(import_to_add) = import_to_add {
+        builder.add_import(import_to_add);
+    }
     builder.build()
 }
 
@@ -247,10 +288,15 @@ fn render_resolution_simple_(
         }),
         ScopeDef::ModuleDef(Const(..)) => CompletionItemKind::SymbolKind(SymbolKind::Const),
         ScopeDef::ModuleDef(Static(..)) => CompletionItemKind::SymbolKind(SymbolKind::Static),
+        ScopeDef::ModuleDef(Trait(it)) if it.is_alias(db) => {
+            CompletionItemKind::SymbolKind(SymbolKind::TraitAlias)
+        }
         ScopeDef::ModuleDef(Trait(..)) => CompletionItemKind::SymbolKind(SymbolKind::Trait),
         ScopeDef::ModuleDef(TypeAlias(..)) => CompletionItemKind::SymbolKind(SymbolKind::TypeAlias),
         ScopeDef::ModuleDef(BuiltinType(..)) => CompletionItemKind::BuiltinType,
         ScopeDef::GenericParam(param) => CompletionItemKind::SymbolKind(match param {
+            // The implicit `Self` parameter of traits
+            hir::GenericParam::TypeParam(it) if it.is_implicit(db) => SymbolKind::SelfParam,
             hir::GenericParam::TypeParam(_) => SymbolKind::TypeParam,
             hir::GenericParam::ConstParam(_) => SymbolKind::ConstParam,
             hir::GenericParam::LifetimeParam(_) => SymbolKind::LifetimeParam,
@@ -264,7 +310,30 @@ fn render_resolution_simple_(
 
     let local_name = local_name.to_smol_str();
     let mut item = CompletionItem::new(kind, ctx.source_range(), local_name.clone());
-    item.set_relevance(ctx.completion_relevance());
+    let relevance = CompletionRelevance {
+        is_prelude: is_prelude_type(ctx.completion, resolution),
+        is_deprecated: scope_def_is_deprecated(&ctx, resolution),
+        fits_generic_params_use: fits_generic_params_use(ctx.completion, resolution),
+        is_recently_used: is_recently_used_type(ctx.completion, resolution),
+        required_generic_params: type_required_generic_params(ctx.completion, resolution),
+        ..ctx.completion_relevance()
+    };
+    item.set_relevance(relevance);
+    let escaped_name = escape_raw_ident(ctx.completion, &local_name);
+    if let Some(escaped_name) = &escaped_name {
+        item.insert_text(escaped_name.clone());
+    }
+    let const_ty = match resolution {
+        ScopeDef::ModuleDef(Const(it)) => Some(it.ty(db)),
+        ScopeDef::GenericParam(hir::GenericParam::ConstParam(it)) => Some(it.ty(db)),
+        _ => None,
+    };
+    if let Some(ty) = const_ty {
+        item.set_relevance(CompletionRelevance {
+            type_match: compute_type_match(ctx.completion, &ty),
+            ..relevance
+        });
+    }
     if let ScopeDef::Local(local) = resolution {
         let ty = local.ty(db);
         if !ty.is_unknown() {
@@ -283,16 +352,57 @@ fn render_resolution_simple_(
         }
     };
 
+    if let ScopeDef::ModuleDef(Adt(_) | TypeAlias(_) | BuiltinType(_))
+    | ScopeDef::GenericParam(hir::GenericParam::TypeParam(_)) = resolution
+    {
+        let is_generic_param_name_match = matches!(resolution, ScopeDef::ModuleDef(TypeAlias(_)))
+            && compute_generic_param_name_match(ctx.completion, &local_name);
+        let is_generic_param_default = is_generic_param_default(ctx.completion, resolution);
+        let satisfies_generic_param_bounds =
+            satisfies_generic_param_bounds(ctx.completion, resolution);
+        item.set_relevance(CompletionRelevance {
+            is_generic_param_name_match,
+            is_generic_param_default,
+            satisfies_generic_param_bounds,
+            ..relevance
+        });
+    }
+    if let ScopeDef::ModuleDef(Adt(it)) = resolution {
+        if let Some(detail) = generic_param_defaults_detail(db, it) {
+            item.detail(detail);
+        }
+    }
+    if let ScopeDef::ModuleDef(Trait(it)) = resolution {
+        // Sealed traits can't be implemented outside of their crate, which is easy to miss.
+        if it.module(db).krate() != ctx.completion.krate && it.is_sealed(db) {
+            item.detail("sealed");
+        }
+    }
+    if let ScopeDef::ModuleDef(TypeAlias(it)) = resolution {
+        if let Some(type_ref) = it.type_ref(db) {
+            // Truncate long aliased types to keep the completion popup readable.
+            item.detail(format!("= {}", type_ref.display_truncated(db, Some(30))));
+        }
+    }
+
     // Add `<>` for generic types
-    let type_path_no_ty_args = matches!(
-        ctx.completion.path_context(),
-        Some(PathCompletionCtx { kind: PathKind::Type { .. }, has_type_args: false, .. })
-    ) && ctx.completion.config.callable.is_some();
+    let (type_path_no_ty_args, is_impl_trait) = match ctx.completion.path_context() {
+        Some(PathCompletionCtx {
+            kind: PathKind::Type { is_impl_trait, .. },
+            has_type_args: false,
+            ..
+        }) => (ctx.completion.config.callable.is_some(), *is_impl_trait),
+        _ => (false, false),
+    };
     if type_path_no_ty_args {
         if let Some(cap) = ctx.snippet_cap() {
             let has_non_default_type_params = match resolution {
                 ScopeDef::ModuleDef(Adt(it)) => it.has_non_default_type_params(db),
                 ScopeDef::ModuleDef(TypeAlias(it)) => it.has_non_default_type_params(db),
+                ScopeDef::ModuleDef(Trait(it)) if is_impl_trait => {
+                    cov_mark::hit!(inserts_angle_brackets_for_generic_impl_traits);
+                    required_generic_params_count(db, it.into()) > 0
+                }
                 _ => false,
             };
             if has_non_default_type_params {
@@ -300,12 +410,14 @@ fn render_resolution_simple_(
                 item.lookup_by(local_name.clone())
                     .label(SmolStr::from_iter([&local_name, "<…>"]))
                     .trigger_call_info()
-                    .insert_snippet(cap, format!("{}<$0>", local_name));
+                    .insert_snippet(
+                        cap,
+                        format!("{}<$0>", escaped_name.as_deref().unwrap_or(&local_name)),
+                    );
             }
         }
     }
-    item.set_documentation(scope_def_docs(db, resolution))
-        .set_deprecated(scope_def_is_deprecated(&ctx, resolution));
+    item.set_documentation(scope_def_docs(db, resolution)).set_deprecated(relevance.is_deprecated);
 
     if let Some(import_to_add) = ctx.import_to_add {
         item.add_import(import_to_add);
@@ -313,6 +425,21 @@ fn render_resolution_simple_(
     item
 }
 
+/// Counts the generic parameters that have to be given explicitly, unlike the implicit `Self`
+/// parameter of traits, parameters with defaults and lifetimes.
+fn required_generic_params_count(db: &RootDatabase, def: hir::GenericDef) -> usize {
+    def.params(db)
+        .into_iter()
+        .filter(|param| match param {
+            hir::GenericParam::TypeParam(it) => {
+                !it.is_implicit(db) && it.default(db).map_or(true, |it| it.is_unknown())
+            }
+            hir::GenericParam::ConstParam(_) => true,
+            hir::GenericParam::LifetimeParam(_) => false,
+        })
+        .count()
+}
+
 fn scope_def_docs(db: &RootDatabase, resolution: ScopeDef) -> Option<hir::Documentation> {
     use hir::ModuleDef::*;
     match resolution {
@@ -329,7 +456,10 @@ fn scope_def_docs(db: &RootDatabase, resolution: ScopeDef) -> Option<hir::Docume
 
 fn scope_def_is_deprecated(ctx: &RenderContext<'_>, resolution: ScopeDef) -> bool {
     match resolution {
-        ScopeDef::ModuleDef(it) => ctx.is_deprecated_assoc_item(it),
+        ScopeDef::ModuleDef(it) => {
+            it.attrs(ctx.db()).map_or(false, |attrs| attrs.by_key("deprecated").exists())
+                || ctx.is_deprecated_assoc_item(it)
+        }
         ScopeDef::GenericParam(it) => ctx.is_deprecated(it),
         ScopeDef::AdtSelfType(it) => ctx.is_deprecated(it),
         _ => false,
@@ -361,6 +491,185 @@ fn compute_exact_name_match(ctx: &CompletionContext, completion_name: &str) -> b
     ctx.expected_name.as_ref().map_or(false, |name| name.text() == completion_name)
 }
 
+/// Returns the raw identifier form of `name` if it collides with a keyword of the current edition.
+fn escape_raw_ident(ctx: &CompletionContext, name: &str) -> Option<String> {
+    let is_keyword = match SyntaxKind::from_keyword(name)? {
+        // These can't be raw identifiers.
+        SyntaxKind::SELF_KW
+        | SyntaxKind::SELF_TYPE_KW
+        | SyntaxKind::CRATE_KW
+        | SyntaxKind::SUPER_KW => false,
+        // These only became keywords in the 2018 edition.
+        SyntaxKind::ASYNC_KW | SyntaxKind::AWAIT_KW | SyntaxKind::DYN_KW | SyntaxKind::TRY_KW => {
+            ctx.krate.edition(ctx.db) >= Edition::Edition2018
+        }
+        _ => true,
+    };
+    is_keyword.then(|| format!("r#{}", name))
+}
+
+/// Checks whether a type's name starts with the name of the generic parameter it is given for,
+/// ignoring case, so that single letter parameter names like `V` match `Value`.
+pub(crate) fn compute_generic_param_name_match(ctx: &CompletionContext, type_name: &str) -> bool {
+    ctx.expected_generic_param_name.as_ref().map_or(false, |param_name| {
+        let param_name = param_name.to_smol_str().to_lowercase();
+        type_name.to_lowercase().starts_with(&param_name)
+    })
+}
+
+/// Checks whether a type completed in type position is one of the items of the prelude.
+fn is_prelude_type(ctx: &CompletionContext, resolution: ScopeDef) -> bool {
+    let in_type_position =
+        matches!(ctx.path_context(), Some(PathCompletionCtx { kind: PathKind::Type { .. }, .. }));
+    in_type_position
+        && matches!(resolution, ScopeDef::ModuleDef(_))
+        && ctx.prelude_defs.contains(&resolution)
+}
+
+/// Checks whether a type completed in type position is one the user referenced recently.
+fn is_recently_used_type(ctx: &CompletionContext, resolution: ScopeDef) -> bool {
+    let def = match resolution {
+        ScopeDef::ModuleDef(
+            def @ (hir::ModuleDef::Adt(_)
+            | hir::ModuleDef::TypeAlias(_)
+            | hir::ModuleDef::BuiltinType(_)
+            | hir::ModuleDef::Trait(_)),
+        ) => def,
+        _ => return false,
+    };
+    let in_type_position =
+        matches!(ctx.path_context(), Some(PathCompletionCtx { kind: PathKind::Type { .. }, .. }));
+    in_type_position && ctx.config.recently_used_types.contains(&def)
+}
+
+/// Returns the number of generic arguments a type completed in type position requires.
+fn type_required_generic_params(ctx: &CompletionContext, resolution: ScopeDef) -> usize {
+    let in_type_position =
+        matches!(ctx.path_context(), Some(PathCompletionCtx { kind: PathKind::Type { .. }, .. }));
+    if !in_type_position {
+        return 0;
+    }
+    let def = match resolution {
+        ScopeDef::ModuleDef(hir::ModuleDef::Adt(it)) => it.into(),
+        ScopeDef::ModuleDef(hir::ModuleDef::TypeAlias(it)) => it.into(),
+        _ => return 0,
+    };
+    required_generic_params_count(ctx.db, def)
+}
+
+/// Checks whether `resolution` fits a position that usually makes use of the generic parameters
+/// of the surrounding item. Where predicates mostly bound them, `PhantomData` mostly marks them as
+/// used, array lengths mostly are const parameters and generic type aliases mostly pass them on to
+/// a generic type, as in `type Foo<T> = Vec<T>;`.
+fn fits_generic_params_use(ctx: &CompletionContext, resolution: ScopeDef) -> bool {
+    if let Some(ImmediateLocation::ArrayLen(_)) = ctx.completion_location {
+        let is_const_param =
+            matches!(resolution, ScopeDef::GenericParam(hir::GenericParam::ConstParam(_)));
+        if is_const_param {
+            cov_mark::hit!(array_len_prefers_const_params);
+        }
+        return is_const_param;
+    }
+    let is_type_param =
+        matches!(resolution, ScopeDef::GenericParam(hir::GenericParam::TypeParam(_)));
+    if ctx.expects_phantom_data_arg {
+        if is_type_param {
+            cov_mark::hit!(phantom_data_arg_prefers_generic_params);
+        }
+        return is_type_param;
+    }
+    if let Some(PathCompletionCtx { kind: PathKind::Type { in_where_pred: true, .. }, .. }) =
+        ctx.path_context()
+    {
+        return is_type_param;
+    }
+    let alias = match &ctx.completion_location {
+        Some(ImmediateLocation::TypeAliasRhs(alias)) => alias,
+        _ => return false,
+    };
+    let alias_has_type_params = alias.generic_param_list().map_or(false, |it| {
+        it.type_or_const_params().any(|it| matches!(it, ast::TypeOrConstParam::Type(_)))
+    });
+    if !alias_has_type_params {
+        return false;
+    }
+    let def = match resolution {
+        ScopeDef::ModuleDef(hir::ModuleDef::Adt(it)) => hir::GenericDef::from(it),
+        ScopeDef::ModuleDef(hir::ModuleDef::TypeAlias(it)) => hir::GenericDef::from(it),
+        _ => return is_type_param,
+    };
+    let is_generic =
+        def.params(ctx.db).into_iter().any(|it| matches!(it, hir::GenericParam::TypeParam(_)));
+    if is_generic {
+        cov_mark::hit!(type_alias_rhs_prefers_generic_types);
+    }
+    is_generic
+}
+
+/// Checks whether a type is the default of the generic parameter it is given for.
+fn is_generic_param_default(ctx: &CompletionContext, resolution: ScopeDef) -> bool {
+    let default = match &ctx.expected_generic_param_default {
+        Some(it) => it,
+        None => return false,
+    };
+    match resolution {
+        ScopeDef::ModuleDef(hir::ModuleDef::Adt(it)) => default.as_adt() == Some(it),
+        ScopeDef::ModuleDef(hir::ModuleDef::TypeAlias(it)) => *default == it.ty(ctx.db),
+        ScopeDef::ModuleDef(hir::ModuleDef::BuiltinType(it)) => *default == it.ty(ctx.db),
+        _ => false,
+    }
+}
+
+/// Checks whether a type satisfies the bounds of the generic parameter it is given for.
+fn satisfies_generic_param_bounds(ctx: &CompletionContext, resolution: ScopeDef) -> bool {
+    let ty = match resolution {
+        ScopeDef::ModuleDef(hir::ModuleDef::Adt(it)) => it.ty(ctx.db),
+        ScopeDef::ModuleDef(hir::ModuleDef::TypeAlias(it)) => it.ty(ctx.db),
+        ScopeDef::ModuleDef(hir::ModuleDef::BuiltinType(it)) => it.ty(ctx.db),
+        _ => return false,
+    };
+    let bounds = &ctx.expected_generic_param_bounds;
+    if bounds.is_empty() || ty.is_unknown() {
+        return false;
+    }
+    // We can't check generic bounds without knowing their arguments, so only consider the others.
+    let is_generic = |trait_: hir::Trait| {
+        hir::GenericDef::from(trait_).params(ctx.db).into_iter().any(|param| match param {
+            hir::GenericParam::TypeParam(it) => !it.is_implicit(ctx.db),
+            hir::GenericParam::ConstParam(_) => true,
+            hir::GenericParam::LifetimeParam(_) => false,
+        })
+    };
+    let mut checked_bounds = bounds.iter().copied().filter(|&it| !is_generic(it)).peekable();
+    checked_bounds.peek().is_some()
+        && checked_bounds.all(|trait_| ty.impls_trait(ctx.db, trait_, &[]))
+}
+
+/// Renders the generic parameters of an ADT if some of them have defaults, like `<T, U = u32>`.
+fn generic_param_defaults_detail(db: &RootDatabase, adt: hir::Adt) -> Option<String> {
+    let mut has_default = false;
+    let params: Vec<_> = hir::GenericDef::from(adt)
+        .params(db)
+        .into_iter()
+        .map(|param| match param {
+            hir::GenericParam::TypeParam(it) => {
+                match it.default(db).filter(|it| !it.is_unknown()) {
+                    Some(default) => {
+                        has_default = true;
+                        format!("{} = {}", it.name(db), default.display(db))
+                    }
+                    None => it.name(db).to_string(),
+                }
+            }
+            hir::GenericParam::ConstParam(it) => {
+                format!("const {}: {}", it.name(db), it.ty(db).display(db))
+            }
+            hir::GenericParam::LifetimeParam(it) => it.name(db).to_string(),
+        })
+        .collect();
+    has_default.then(|| format!("<{}>", params.join(", ")))
+}
+
 fn compute_ref_match(
     ctx: &CompletionContext,
     completion_ty: &hir::Type,
@@ -391,8 +700,9 @@ mod tests {
 
     use crate::{
         item::CompletionRelevanceTypeMatch,
-        tests::{check_edit, do_completion, get_all_items, TEST_CONFIG},
-        CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevancePostfixMatch,
+        tests::{check_edit, do_completion, get_all_items, position, TEST_CONFIG},
+        CompletionConfig, CompletionItem, CompletionItemKind, CompletionRelevance,
+        CompletionRelevancePostfixMatch,
     };
 
     #[track_caller]
@@ -418,7 +728,37 @@ mod tests {
 
     #[track_caller]
     fn check_relevance(ra_fixture: &str, expect: Expect) {
-        let mut actual = get_all_items(TEST_CONFIG, ra_fixture, None);
+        check_relevance_with_config(TEST_CONFIG, ra_fixture, expect)
+    }
+
+    #[track_caller]
+    fn check_relevance_with_config(config: CompletionConfig, ra_fixture: &str, expect: Expect) {
+        let mut actual = get_all_items(config, ra_fixture, None);
+        actual.retain(|it| it.kind() != CompletionItemKind::Snippet);
+        actual.retain(|it| it.kind() != CompletionItemKind::Keyword);
+        actual.retain(|it| it.kind() != CompletionItemKind::BuiltinType);
+        actual.sort_by_key(|it| cmp::Reverse(it.relevance().score()));
+        check_relevance_(actual, expect);
+    }
+
+    /// Checks the relevance of the completions with the items of the crate root named `recent`
+    /// injected as the recently used types.
+    #[track_caller]
+    fn check_relevance_with_recently_used(ra_fixture: &str, recent: &[&str], expect: Expect) {
+        let (db, position) = position(ra_fixture);
+        let root = hir::Crate::all(&db)[0].root_module(&db);
+        let recently_used_types = recent
+            .iter()
+            .filter_map(|&recent| {
+                root.scope(&db, None).into_iter().find_map(|(name, def)| match def {
+                    hir::ScopeDef::ModuleDef(it) if name.to_smol_str() == recent => Some(it),
+                    _ => None,
+                })
+            })
+            .collect();
+        let config = CompletionConfig { recently_used_types, ..TEST_CONFIG };
+        let mut actual: Vec<CompletionItem> =
+            crate::completions(&db, &config, position, None).unwrap().into();
         actual.retain(|it| it.kind() != CompletionItemKind::Snippet);
         actual.retain(|it| it.kind() != CompletionItemKind::Keyword);
         actual.retain(|it| it.kind() != CompletionItemKind::BuiltinType);
@@ -465,6 +805,16 @@ mod tests {
                 ),
                 (relevance.is_op_method, "op_method"),
                 (relevance.requires_import, "requires_import"),
+                (relevance.is_definite, "definite"),
+                (relevance.is_prelude, "prelude"),
+                (relevance.is_deprecated, "deprecated"),
+                (relevance.is_recently_used, "recent"),
+                (relevance.fits_generic_params_use, "generic_use"),
+                (relevance.satisfies_generic_param_bounds, "generic_bounds"),
+                (relevance.is_generic_param_default, "generic_default"),
+                (relevance.is_generic_param_name_match, "generic_name"),
+                (relevance.required_generic_params > 0, "generic_params"),
+                (relevance.is_syntax_snippet, "syntax_snippet"),
             ]
             .into_iter()
             .filter_map(|(cond, desc)| if cond { Some(desc) } else { None })
@@ -648,6 +998,15 @@ fn main() { let _: m::Spam = S$0 }
                             requires_import: false,
                             is_op_method: false,
                             is_private_editable: false,
+                            is_prelude: false,
+                            is_deprecated: false,
+                            is_recently_used: false,
+                            fits_generic_params_use: false,
+                            satisfies_generic_param_bounds: false,
+                            is_generic_param_default: false,
+                            is_generic_param_name_match: false,
+                            required_generic_params: 0,
+                            is_syntax_snippet: false,
                             postfix_match: None,
                             is_definite: false,
                         },
@@ -673,6 +1032,15 @@ fn main() { let _: m::Spam = S$0 }
                             requires_import: false,
                             is_op_method: false,
                             is_private_editable: false,
+                            is_prelude: false,
+                            is_deprecated: false,
+                            is_recently_used: false,
+                            fits_generic_params_use: false,
+                            satisfies_generic_param_bounds: false,
+                            is_generic_param_default: false,
+                            is_generic_param_name_match: false,
+                            required_generic_params: 0,
+                            is_syntax_snippet: false,
                             postfix_match: None,
                             is_definite: false,
                         },
@@ -750,6 +1118,15 @@ fn foo() { A { the$0 } }
                             requires_import: false,
                             is_op_method: false,
                             is_private_editable: false,
+                            is_prelude: false,
+                            is_deprecated: false,
+                            is_recently_used: false,
+                            fits_generic_params_use: false,
+                            satisfies_generic_param_bounds: false,
+                            is_generic_param_default: false,
+                            is_generic_param_name_match: false,
+                            required_generic_params: 0,
+                            is_syntax_snippet: false,
                             postfix_match: None,
                             is_definite: false,
                         },
@@ -1667,4 +2044,448 @@ fn f() {
             "#]],
         );
     }
+
+    #[test]
+    fn inferred_type_relevance() {
+        check_relevance(
+            r#"
+struct MyStruct;
+struct Other;
+fn f() -> My$0 {
+    MyStruct
+}
+"#,
+            expect![[r#"
+                it MyStruct [definite]
+                st MyStruct []
+                st Other []
+            "#]],
+        );
+        check_relevance(
+            r#"
+struct MyStruct;
+struct Other;
+fn f() -> Ot$0 {
+    MyStruct
+}
+"#,
+            expect![[r#"
+                st MyStruct []
+                st Other []
+                it MyStruct []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn const_generic_arg_prefers_matching_consts() {
+        check_relevance(
+            r#"
+struct Arr<const N: usize>;
+const SIZE: usize = 0;
+const FLAG: bool = false;
+fn f() -> Arr<$0> {}
+"#,
+            expect![[r#"
+                ct SIZE [type]
+                ct FLAG []
+                st Arr [generic_params]
+            "#]],
+        );
+        check_relevance(
+            r#"
+struct Arr<'a, T, const N: usize>(&'a T);
+const SIZE: usize = 0;
+const FLAG: bool = false;
+fn f<const P: usize>() -> Arr<'static, u8, $0> {}
+"#,
+            expect![[r#"
+                cp P [type]
+                ct SIZE [type]
+                ct FLAG []
+                st Arr<…> [generic_params]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_syntax_snippets_rank_below_names() {
+        check_relevance_for_kinds(
+            r#"
+struct S;
+fn f(_: $0) {}
+"#,
+            &[CompletionItemKind::Snippet, CompletionItemKind::SymbolKind(SymbolKind::Struct)],
+            expect![[r#"
+                st S []
+                sn tuple [syntax_snippet]
+                sn array [syntax_snippet]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn generic_arg_prefers_types_matching_param_name() {
+        check_relevance(
+            r#"
+struct HashMap<K, V>(K, V);
+struct Key;
+struct Value;
+struct Other;
+type V = ();
+type Val = ();
+type Key2 = ();
+fn f() -> HashMap<Key, $0> {}
+"#,
+            expect![[r#"
+                ta Val [generic_name]
+                ta V [generic_name]
+                st Key []
+                st Value []
+                ta Key2 []
+                st Other []
+                st HashMap<…> [generic_params]
+            "#]],
+        );
+        check_relevance(
+            r#"
+struct HashMap<Key, Value>(Key, Value);
+trait Tr { type Value; type Valuable; }
+fn f<T: Tr>() -> HashMap<(), T::$0> {}
+"#,
+            expect![[r#"
+                as Value (as Tr) [generic_name]
+                as Valuable (as Tr) []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn generic_arg_prefers_default_of_param() {
+        check_relevance(
+            r#"
+struct Wrapper<T = Inner>(T);
+struct Inner;
+struct Other;
+fn f() -> Wrapper<$0> {}
+"#,
+            expect![[r#"
+                st Inner [generic_default]
+                st Wrapper []
+                st Other []
+            "#]],
+        );
+        check_relevance(
+            r#"
+struct Wrapper<T, U = Inner>(T, U);
+struct Inner;
+fn f() -> Wrapper<$0, Inner> {}
+"#,
+            expect![[r#"
+                st Inner []
+                st Wrapper<…> [generic_params]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn prelude_items_relevance() {
+        check_relevance(
+            r#"
+//- /main.rs crate:main deps:std
+struct Local;
+fn f(_: $0) {}
+//- /std/lib.rs crate:std
+pub mod prelude {
+    pub mod rust_2018 {
+        pub use crate::{option::Option, vec::Vec};
+    }
+}
+pub mod option {
+    pub enum Option<T> { None, Some(T) }
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+}
+"#,
+            expect![[r#"
+                st Local []
+                md std []
+                en Option<…> [prelude+generic_params]
+                st Vec<…> [prelude+generic_params]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn generic_arg_prefers_types_satisfying_param_bounds() {
+        check_relevance(
+            r#"
+trait Hash {}
+struct HashMap<K, V: Hash>(K, V);
+struct Hashable;
+impl Hash for Hashable {}
+struct Other;
+fn f() -> HashMap<Other, $0> {}
+"#,
+            expect![[r#"
+                st Hashable [generic_bounds]
+                tt Hash []
+                st Other []
+                st HashMap<…> [generic_params]
+            "#]],
+        );
+        // The first parameter has no bounds.
+        check_relevance(
+            r#"
+trait Hash {}
+struct HashMap<K, V: Hash>(K, V);
+struct Hashable;
+impl Hash for Hashable {}
+fn f() -> HashMap<$0, Hashable> {}
+"#,
+            expect![[r#"
+                st Hashable []
+                tt Hash []
+                st HashMap<…> [generic_params]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn deprecated_types_relevance() {
+        check_relevance(
+            r#"
+#[deprecated]
+struct Old;
+struct New;
+fn f(_: $0) {}
+"#,
+            expect![[r#"
+                st New []
+                st Old [deprecated]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn result_error_arg_prefers_error_types() {
+        check_relevance(
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:std
+use core::result::Result;
+use std::error::Error;
+struct Foo;
+struct MyError;
+impl Error for MyError {}
+fn f() -> Result<Foo, $0> {}
+//- /std.rs crate:std
+pub mod error {
+    pub trait Error {}
+}
+"#,
+            expect![[r#"
+                st MyError [generic_bounds]
+                st Foo []
+                tt Error []
+                md std []
+                md core []
+                en Result<…> [generic_params]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn where_pred_subject_prefers_generic_params() {
+        check_relevance(
+            r#"
+struct Foo;
+fn f<T>() where $0 {}
+"#,
+            expect![[r#"
+                tp T [generic_use]
+                st Foo []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_alias_rhs_prefers_generic_types() {
+        cov_mark::check!(type_alias_rhs_prefers_generic_types);
+        check_relevance(
+            r#"
+struct Unit;
+struct Wrapper<T>(T);
+type Pair<A, B> = (A, B);
+type Foo<T> = $0;
+"#,
+            expect![[r#"
+                tp T [generic_use]
+                ta Foo<…> [generic_use+generic_params]
+                st Unit []
+                st Wrapper<…> [generic_use+generic_params]
+                ta Pair<…> [generic_use+generic_params]
+            "#]],
+        );
+        // Without generic parameters of its own the alias has nothing to pass on.
+        check_relevance(
+            r#"
+struct Unit;
+struct Wrapper<T>(T);
+type Foo = $0;
+"#,
+            expect![[r#"
+                ta Foo []
+                st Unit []
+                st Wrapper<…> [generic_params]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn phantom_data_arg_prefers_generic_params() {
+        cov_mark::check!(phantom_data_arg_prefers_generic_params);
+        check_relevance(
+            r#"
+//- minicore: phantom_data
+use core::marker::PhantomData;
+struct Unit;
+struct S<T> { _p: PhantomData<$0> }
+"#,
+            expect![[r#"
+                tp T [generic_use]
+                sp Self []
+                st Unit []
+                md core []
+                st S<…> [generic_params]
+                st PhantomData<…> [generic_params]
+                tt Sized [prelude]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn recently_used_types_are_preferred() {
+        check_relevance_with_recently_used(
+            r#"
+struct Alpha;
+struct Beta;
+struct Gamma;
+trait Trait {}
+fn f(_: $0) {}
+"#,
+            &["Trait", "Beta"],
+            expect![[r#"
+                tt Trait [recent]
+                st Beta [recent]
+                st Alpha []
+                st Gamma []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn where_pred_eq_rhs_prefers_types_satisfying_assoc_type_bounds() {
+        check_relevance(
+            r#"
+//- minicore: copy
+struct Big;
+struct Small;
+impl Copy for Small {}
+trait Tr { type Item: Copy; }
+fn f<T: Tr>() where T::Item = $0 {}
+"#,
+            expect![[r#"
+                st Small [generic_bounds]
+                tp T []
+                st Big []
+                tt Tr []
+                md core []
+                tt Copy [prelude]
+                tt Sized [prelude]
+                tt Clone [prelude]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn inserts_angle_brackets_for_generic_impl_traits() {
+        cov_mark::check!(inserts_angle_brackets_for_generic_impl_traits);
+        check_edit(
+            "From",
+            r#"
+//- minicore: from
+struct S;
+impl Fr$0 for S {}
+"#,
+            r#"
+struct S;
+impl From<$0> for S {}
+"#,
+        );
+        check_edit(
+            "Add",
+            r#"
+trait Add<Rhs = Self> {}
+struct S;
+impl Ad$0 for S {}
+"#,
+            r#"
+trait Add<Rhs = Self> {}
+struct S;
+impl Add for S {}
+"#,
+        );
+        check_edit(
+            "Wrapper",
+            r#"
+trait Tr<T> {}
+struct Wrapper<T>(T);
+impl<T> Tr<T> for Wra$0 {}
+"#,
+            r#"
+trait Tr<T> {}
+struct Wrapper<T>(T);
+impl<T> Tr<T> for Wrapper<$0> {}
+"#,
+        );
+    }
+
+    #[test]
+    fn array_len_prefers_const_params() {
+        cov_mark::check!(array_len_prefers_const_params);
+        check_relevance(
+            r#"
+const C: usize = 0;
+fn g() {}
+fn f<const N: usize>() -> [u8; $0] {}
+"#,
+            expect![[r#"
+                cp N [type+generic_use]
+                ct C [type]
+                fn g() []
+                fn f() []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn types_needing_few_generic_args_are_preferred() {
+        check_relevance(
+            r#"
+struct Mat<T, U, V>(T, U, V);
+struct Mad<T, U>(T, U);
+struct Max<T>(T);
+struct Map;
+fn f(_: Ma$0) {}
+"#,
+            expect![[r#"
+                st Map []
+                st Max<…> [generic_params]
+                st Mad<…> [generic_params]
+                st Mat<…> [generic_params]
+            "#]],
+        );
+    }
 }
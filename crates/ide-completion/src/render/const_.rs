@@ -2,24 +2,51 @@
 
 use hir::{AsAssocItem, HirDisplay};
 use ide_db::SymbolKind;
+use syntax::SmolStr;
 
-use crate::{item::CompletionItem, render::RenderContext};
+use crate::{
+    item::CompletionItem,
+    render::{compute_type_match, RenderContext},
+    CompletionRelevance,
+};
 
 pub(crate) fn render_const(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem> {
     let _p = profile::span("render_const");
-    render(ctx, const_)
+    render(ctx, const_, None)
 }
 
-fn render(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem> {
+/// Renders an associated const together with the path of the type it is associated with, like
+/// `Foo::CONST`.
+pub(crate) fn render_qualified_const(
+    ctx: RenderContext<'_>,
+    const_: hir::Const,
+    qualifier: &str,
+) -> Option<CompletionItem> {
+    let _p = profile::span("render_qualified_const");
+    render(ctx, const_, Some(qualifier))
+}
+
+fn render(
+    ctx: RenderContext<'_>,
+    const_: hir::Const,
+    qualifier: Option<&str>,
+) -> Option<CompletionItem> {
     let db = ctx.db();
     let name = const_.name(db)?.to_smol_str();
+    let name = match qualifier {
+        Some(qualifier) => SmolStr::from(format!("{}::{}", qualifier, name)),
+        None => name,
+    };
     let detail = const_.display(db).to_string();
 
     let mut item = CompletionItem::new(SymbolKind::Const, ctx.source_range(), name.clone());
     item.set_documentation(ctx.docs(const_))
         .set_deprecated(ctx.is_deprecated(const_) || ctx.is_deprecated_assoc_item(const_))
         .detail(detail)
-        .set_relevance(ctx.completion_relevance());
+        .set_relevance(CompletionRelevance {
+            type_match: compute_type_match(ctx.completion, &const_.ty(db)),
+            ..ctx.completion_relevance()
+        });
 
     if let Some(actm) = const_.as_assoc_item(db) {
         if let Some(trt) = actm.containing_trait_or_trait_impl(db) {
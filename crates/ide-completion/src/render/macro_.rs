@@ -229,6 +229,32 @@ fn main() { foo! {$0} }
         )
     }
 
+    #[test]
+    fn inserts_macro_call_parens_in_type_position() {
+        check_edit(
+            "vec!",
+            r#"
+macro_rules! vec { () => () }
+type X = vec$0
+"#,
+            r#"
+macro_rules! vec { () => () }
+type X = vec!($0)
+"#,
+        );
+        check_edit(
+            "my_type!",
+            r#"
+macro_rules! my_type { () => { u32 } }
+fn f(_: $0) {}
+"#,
+            r#"
+macro_rules! my_type { () => { u32 } }
+fn f(_: my_type!($0)) {}
+"#,
+        );
+    }
+
     #[test]
     fn completes_macro_call_if_cursor_at_bang_token() {
         // Regression test for https://github.com/rust-analyzer/rust-analyzer/issues/9904
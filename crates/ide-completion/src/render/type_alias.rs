@@ -2,9 +2,14 @@
 
 use hir::{AsAssocItem, HirDisplay};
 use ide_db::SymbolKind;
+use itertools::Itertools;
 use syntax::SmolStr;
 
-use crate::{item::CompletionItem, render::RenderContext};
+use crate::{
+    context::{PathCompletionCtx, PathKind},
+    item::{CompletionItem, CompletionItemKind, CompletionRelevance},
+    render::{compute_generic_param_name_match, RenderContext},
+};
 
 pub(crate) fn render_type_alias(
     ctx: RenderContext<'_>,
@@ -35,17 +40,46 @@ fn render(
         type_alias.name(db).to_smol_str()
     };
     let detail = type_alias.display(db).to_string();
+    let assoc_item = type_alias.as_assoc_item(db);
 
-    let mut item = CompletionItem::new(SymbolKind::TypeAlias, ctx.source_range(), name.clone());
+    let kind = match assoc_item {
+        Some(_) => CompletionItemKind::AssocType,
+        None => CompletionItemKind::SymbolKind(SymbolKind::TypeAlias),
+    };
+    let mut item = CompletionItem::new(kind, ctx.source_range(), name.clone());
     item.set_documentation(ctx.docs(type_alias))
         .set_deprecated(ctx.is_deprecated(type_alias) || ctx.is_deprecated_assoc_item(type_alias))
         .detail(detail)
-        .set_relevance(ctx.completion_relevance());
+        .set_relevance(CompletionRelevance {
+            is_generic_param_name_match: compute_generic_param_name_match(
+                ctx.completion,
+                &type_alias.name(db).to_smol_str(),
+            ),
+            ..ctx.completion_relevance()
+        });
 
-    if let Some(actm) = type_alias.as_assoc_item(db) {
+    if let Some(actm) = assoc_item {
         if let Some(trt) = actm.containing_trait_or_trait_impl(db) {
             item.trait_name(trt.name(db).to_smol_str());
-            item.insert_text(name);
+            item.insert_text(name.clone());
+        }
+    }
+
+    // Generic associated types need their own arguments, so add placeholders for them.
+    let type_path_no_ty_args = matches!(
+        ctx.completion.path_context(),
+        Some(PathCompletionCtx { kind: PathKind::Type { .. }, has_type_args: false, .. })
+    );
+    if let Some(cap) = ctx.snippet_cap().filter(|_| !with_eq && type_path_no_ty_args) {
+        let params = hir::GenericDef::from(type_alias).params(db);
+        if assoc_item.is_some() && !params.is_empty() {
+            cov_mark::hit!(inserts_gat_args);
+            let placeholders = params
+                .iter()
+                .enumerate()
+                .map(|(idx, param)| format!("${{{}:{}}}", idx + 1, param.name(db)))
+                .join(", ");
+            item.insert_snippet(cap, format!("{}<{}>", name, placeholders));
         }
     }
 
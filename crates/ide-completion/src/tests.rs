@@ -66,6 +66,8 @@ pub(crate) const TEST_CONFIG: CompletionConfig = CompletionConfig {
     enable_imports_on_the_fly: true,
     enable_self_on_the_fly: true,
     enable_private_editable: false,
+    enable_private_items_in_crate: false,
+    enable_smart_pointer_inferred_types: false,
     callable: Some(CallableSnippets::FillArguments),
     snippet_cap: SnippetCap::new(true),
     insert_use: InsertUseConfig {
@@ -76,6 +78,8 @@ pub(crate) const TEST_CONFIG: CompletionConfig = CompletionConfig {
         skip_glob_imports: true,
     },
     snippets: Vec::new(),
+    excluded_crates: Vec::new(),
+    recently_used_types: Vec::new(),
 };
 
 pub(crate) fn completion_list(ra_fixture: &str) -> String {
@@ -684,6 +684,28 @@ mod derive {
         );
     }
 
+    #[test]
+    fn derive_excludes_other_macros() {
+        check_derive(
+            r#"
+//- proc_macros: identity, mirror, derive_identity
+//- minicore: derive, clone, fmt
+use proc_macros::{identity, mirror, DeriveIdentity};
+macro_rules! makro { () => {} }
+#[derive($0)] struct Test;
+"#,
+            expect![[r#"
+                de Clone          macro Clone
+                de DeriveIdentity proc_macro DeriveIdentity
+                md core
+                md proc_macros
+                kw crate::
+                kw self::
+                kw super::
+            "#]],
+        );
+    }
+
     #[test]
     fn derive_with_input_before() {
         check_derive(
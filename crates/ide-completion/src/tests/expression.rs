@@ -560,10 +560,10 @@ fn func() {
         expect![[r#"
             ct ASSOC_CONST const ASSOC_CONST: ()
             fn assoc_fn()  fn()
-            ta AssocType   type AssocType = ()
             ev RecordV {…} RecordV { field: u32 }
             ev TupleV(…)   TupleV(u32)
             ev UnitV       UnitV
+            as AssocType   type AssocType = ()
         "#]],
     );
 }
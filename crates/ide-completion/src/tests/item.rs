@@ -47,12 +47,13 @@ impl Trait for Str$0
             st Record
             st Tuple
             st Unit
-            tt Trait
             un Union
             bt u32
             kw crate::
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     )
 }
@@ -19,16 +19,20 @@ struct Foo<'lt, T, const C: usize> where $0 {}
             en Enum
             ma makro!(…) macro_rules! makro
             md module
+            sp Self
             st Foo<…>
             st Record
             st Tuple
             st Unit
             tt Trait
+            tp T
             un Union
             bt u32
             kw crate::
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -43,32 +47,88 @@ struct Foo<'lt, T, const C: usize> where T: $0 {}
             ma makro!(…) macro_rules! makro
             md module
             tt Trait
+            kw ?Sized
             kw crate::
             kw self::
             kw super::
+            sn for<'a>
         "#]],
     );
 }
 
 #[test]
-fn bound_for_lifetime_pred() {
-    // FIXME: should only show lifetimes here, that is we shouldn't get any completions here when not typing
-    // a `'`
+fn bound_for_fn_type_pred() {
     check(
         r#"
-struct Foo<'lt, T, const C: usize> where 'lt: $0 {}
+fn f<T>() where T: $0 {}
 "#,
         expect![[r#"
             ma makro!(…) macro_rules! makro
             md module
             tt Trait
+            kw ?Sized
             kw crate::
             kw self::
             kw super::
+            sn for<'a>
+        "#]],
+    );
+    check(
+        r#"
+mod m {
+    pub struct S;
+    pub trait Tr {}
+}
+fn f<T>() where T: m::$0 {}
+"#,
+        expect![[r#"
+            tt Tr
         "#]],
     );
 }
 
+#[test]
+fn bound_for_lifetime_pred() {
+    check(
+        r#"
+struct Foo<'lt, T, const C: usize> where 'lt: $0 {}
+"#,
+        expect![[r#"
+            lt 'static
+        "#]],
+    );
+}
+
+#[test]
+fn completes_only_lifetimes_in_lifetime_bounds() {
+    cov_mark::check!(complete_lifetimes_in_lifetime_bound);
+    let actual = completion_list(
+        r#"
+trait Trait {}
+struct S;
+fn f<'a, 'b>() where 'a: $0 {}
+"#,
+    );
+    expect![[r#"
+        lt 'b
+        lt 'static
+    "#]]
+    .assert_eq(&actual);
+    let actual = completion_list(
+        r#"
+trait Trait {}
+struct S;
+fn f<'a, 'b, 'c>() where 'a: 'b + $0 {}
+"#,
+    );
+    expect![[r#"
+        lt 'b
+        lt 'c
+        lt 'static
+    "#]]
+    .assert_eq(&actual);
+}
+
 #[test]
 fn bound_for_for_pred() {
     check(
@@ -79,9 +139,11 @@ struct Foo<'lt, T, const C: usize> where for<'a> T: $0 {}
             ma makro!(…) macro_rules! makro
             md module
             tt Trait
+            kw ?Sized
             kw crate::
             kw self::
             kw super::
+            sn for<'a>
         "#]],
     );
 }
@@ -96,16 +158,20 @@ struct Foo<'lt, T, const C: usize> where for<'a> $0 {}
             en Enum
             ma makro!(…) macro_rules! makro
             md module
+            sp Self
             st Foo<…>
             st Record
             st Tuple
             st Unit
             tt Trait
+            tp T
             un Union
             bt u32
             kw crate::
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -132,6 +198,34 @@ impl Record {
             kw crate::
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
+
+#[test]
+fn self_bound_of_trait_fn() {
+    // `Self` is the bounded type here rather than a qualifier, so traits are offered.
+    let actual = completion_list(
+        r#"
+//- minicore: sized, copy, clone
+trait Tr {}
+trait T { type Assoc; fn f() where Self: $0; }
+"#,
+    );
+    expect![[r#"
+        md core
+        tt Clone
+        tt Copy
+        tt Sized
+        tt T
+        tt Tr
+        kw ?Sized
+        kw crate::
+        kw self::
+        kw super::
+        sn for<'a>
+    "#]]
+    .assert_eq(&actual);
+}
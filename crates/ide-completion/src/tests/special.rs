@@ -206,7 +206,7 @@ fn foo() { let _ = lib::S::$0 }
         expect![[r#"
                 ct PUBLIC_CONST    pub const PUBLIC_CONST: u32
                 fn public_method() fn()
-                ta PublicType      pub type PublicType = u32
+                as PublicType      pub type PublicType = u32
             "#]],
     );
 }
@@ -299,8 +299,8 @@ fn foo<T: Sub>() { T::$0 }
                 ct CONST (as Super)      const CONST: u8
                 fn func() (as Super)     fn()
                 fn subfunc() (as Sub)    fn()
-                ta SubTy (as Sub)        type SubTy
-                ta Ty (as Super)         type Ty
+                as SubTy (as Sub)        type SubTy
+                as Ty (as Super)         type Ty
                 me method(…) (as Super)  fn(&self)
                 me submethod(…) (as Sub) fn(&self)
             "#]],
@@ -339,8 +339,8 @@ impl<T> Sub for Wrap<T> {
                 ct CONST (as Super)      const CONST: u8
                 fn func() (as Super)     fn()
                 fn subfunc() (as Sub)    fn()
-                ta SubTy (as Sub)        type SubTy
-                ta Ty (as Super)         type Ty
+                as SubTy (as Sub)        type SubTy
+                as Ty (as Super)         type Ty
                 me method(…) (as Super)  fn(&self)
                 me submethod(…) (as Sub) fn(&self)
             "#]],
@@ -1,13 +1,29 @@
 //! Completion tests for type position.
 use expect_test::{expect, Expect};
+use ide_db::base_db::CrateName;
+use itertools::Itertools;
 
-use crate::tests::{completion_list, BASE_ITEMS_FIXTURE};
+use crate::{
+    completions::r#type::inferred_type_string,
+    completions_in_type_position, completions_in_type_position_with_sink,
+    context::CompletionContext,
+    tests::{
+        check_edit, check_edit_with_config, completion_list, get_all_items, position,
+        BASE_ITEMS_FIXTURE, TEST_CONFIG,
+    },
+    CompletionConfig, CompletionItem, CompletionItemKind, CompletionSink,
+};
 
 fn check(ra_fixture: &str, expect: Expect) {
     let actual = completion_list(&format!("{}\n{}", BASE_ITEMS_FIXTURE, ra_fixture));
     expect.assert_eq(&actual)
 }
 
+fn check_no_base_items(ra_fixture: &str, expect: Expect) {
+    let actual = completion_list(ra_fixture);
+    expect.assert_eq(&actual)
+}
+
 #[test]
 fn record_field_ty() {
     check(
@@ -30,8 +46,11 @@ struct Foo<'lt, T, const C: usize> {
             un Union
             bt u32
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     )
 }
@@ -56,11 +75,14 @@ struct Foo<'lt, T, const C: usize>(f$0);
             un Union
             bt u32
             kw crate::
+            kw dyn
             kw pub
             kw pub(crate)
             kw pub(super)
             kw self::
             kw super::
+            sn array      [T; N]
+            sn tuple      (T, U)
         "#]],
     )
 }
@@ -82,9 +104,13 @@ fn x<'lt, T, const C: usize>() -> $0
             tp T
             un Union
             bt u32
+            kw !
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -109,8 +135,170 @@ const FOO: $0 = Foo(2);
             bt u32
             it Foo<i32>
             kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn inferred_type_static() {
+    check(
+        r#"
+struct Foo<T>(T);
+fn compute() -> Foo<u8> { Foo(0) }
+static FOO: $0 = compute();
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Foo<…>
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            it Foo<u8>
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    check(
+        r#"
+struct Foo<T>(T);
+fn compute() -> Foo<u8> { Foo(0) }
+static mut FOO: $0 = compute();
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Foo<…>
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            it Foo<u8>
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn inferred_type_destructuring_let() {
+    check(
+        r#"
+struct Wrapper(u8);
+fn make() -> Wrapper { Wrapper(0) }
+fn f() {
+    let Wrapper(x): $0 = make();
+}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Tuple
+            st Unit
+            st Wrapper
+            tt Trait
+            un Union
+            bt u32
+            it Wrapper
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn inferred_type_generic_arg() {
+    cov_mark::check!(complete_inferred_generic_arg_type);
+    check(
+        r#"
+enum Option<T> { Some(T), None }
+struct Foo;
+fn make_foo() -> Foo { Foo }
+fn f() {
+    let x: Option<$0> = Option::Some(make_foo());
+}
+"#,
+        expect![[r#"
+            ct CONST
+            en Enum
+            en Option<…>
+            ma makro!(…) macro_rules! makro
+            md module
+            st Foo
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            it Foo
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    check(
+        r#"
+enum Option<T> { Some(T), None }
+struct Foo;
+struct Pair<A, B>(A, B);
+fn f() {
+    let x: Pair<u8, Option<$0>> = Pair(0, Option::Some(Foo));
+}
+"#,
+        expect![[r#"
+            ct CONST
+            en Enum
+            en Option<…>
+            ma makro!(…) macro_rules! makro
+            md module
+            st Foo
+            st Pair<…>
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            it Foo
+            kw _
+            kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -136,8 +324,64 @@ fn f2() {
             bt u32
             it i32
             kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn inferred_type_closure_param_of_iterator_map() {
+    let inferred = get_all_items(
+        TEST_CONFIG,
+        r#"
+//- minicore: fn
+struct Foo;
+struct Iter;
+impl Iter {
+    fn map<B, F: FnMut(Foo) -> B>(self, f: F) -> Map<F> { loop {} }
+}
+struct Map<F>(F);
+fn f() {
+    Iter.map(|x: $0| {});
+}
+"#,
+        None,
+    )
+    .into_iter()
+    .filter(|it| it.kind() == CompletionItemKind::InferredType)
+    .map(|it| it.label().to_owned())
+    .collect::<Vec<_>>();
+    assert_eq!(inferred, ["Foo"]);
+}
+
+#[test]
+fn no_inferred_type_for_unconstrained_closure_param() {
+    check(
+        r#"
+fn f() {
+    let c = |x: $0| ();
+}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -164,9 +408,13 @@ fn f2() {
             un Union
             bt u32
             it u64
+            kw !
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -190,9 +438,13 @@ fn f2(x: u64) -> $0 {
             un Union
             bt u32
             it u64
+            kw !
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -218,8 +470,11 @@ fn f2(x: $0) {
             bt u32
             it i32
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -241,7 +496,7 @@ fn foo<'lt, T, const C: usize>() {
 "#,
         expect![[r#"
             en Enum
-            ma makro!(…)           macro_rules! makro
+            ma makro!(…)     macro_rules! makro
             md a
             md module
             st Record
@@ -251,14 +506,93 @@ fn foo<'lt, T, const C: usize>() {
             tp T
             un Union
             bt u32
-            it a::Foo<a::Foo<i32>>
+            it Foo<Foo<i32>>
+            kw _
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array         [T; N]
+            sn tuple         (T, U)
         "#]],
     );
 }
 
+fn check_inferred_type_string(ra_fixture: &str, expect: Expect) {
+    let (db, position) = position(ra_fixture);
+    let config = TEST_CONFIG;
+    let ctx = CompletionContext::new(&db, position, &config).unwrap();
+    let actual = inferred_type_string(&ctx).unwrap_or_else(|| "?".to_owned());
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn inferred_type_string_is_valid_in_current_module() {
+    check_inferred_type_string(
+        r#"
+mod a {
+    pub struct Foo<T>(T);
+    pub fn x() -> Foo<Foo<i32>> {
+        Foo(Foo(2))
+    }
+}
+fn foo() {
+    let foo: $0 = a::x();
+}
+"#,
+        expect!["a::Foo<a::Foo<i32>>"],
+    );
+    check_inferred_type_string(
+        r#"
+fn g() -> (u8, bool) { (0, true) }
+fn foo() {
+    let foo: $0 = g();
+}
+"#,
+        expect!["(u8, bool)"],
+    );
+    check_inferred_type_string(
+        r#"
+struct S;
+impl S {
+    fn f(self: $0) {}
+}
+"#,
+        expect!["?"],
+    );
+}
+
+#[test]
+fn inferred_type_adds_import() {
+    check_edit(
+        "Foo<Foo<i32>>",
+        r#"
+mod a {
+    pub struct Foo<T>(T);
+    pub fn x() -> Foo<Foo<i32>> {
+        Foo(Foo(2))
+    }
+}
+fn foo() {
+    let foo: $0 = a::x();
+}
+"#,
+        r#"
+use a::Foo;
+
+mod a {
+    pub struct Foo<T>(T);
+    pub fn x() -> Foo<Foo<i32>> {
+        Foo(Foo(2))
+    }
+}
+fn foo() {
+    let foo: Foo<Foo<i32>> = a::x();
+}
+"#,
+    );
+}
+
 #[test]
 fn inferred_type_let() {
     check(
@@ -282,9 +616,13 @@ fn foo<'lt, T, const C: usize>() {
             un Union
             bt u32
             it Foo<i32>
+            kw _
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -309,9 +647,13 @@ fn foo<'lt, T, const C: usize>() {
             tp T
             un Union
             bt u32
+            kw _
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
     check(
@@ -360,14 +702,17 @@ fn foo<'lt, T: Trait2<$0>, const CONST_PARAM: usize>(_: T) {}
             tt Trait
             tt Trait1
             tt Trait2
-            ta Foo =  (as Trait2)   type Foo
-            ta Super =  (as Trait1) type Super
             tp T
             un Union
+            as Foo =  (as Trait2)   type Foo
+            as Super =  (as Trait1) type Super
             bt u32
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array                [T; N]
+            sn tuple                (T, U)
         "#]],
     );
     check(
@@ -409,7 +754,6 @@ impl Tr<$0
             en Enum
             ma makro!(…) macro_rules! makro
             md module
-            sp Self
             st Record
             st S
             st Tuple
@@ -419,8 +763,11 @@ impl Tr<$0
             un Union
             bt u32
             kw crate::
+            kw dyn
             kw self::
             kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
         "#]],
     );
 }
@@ -437,7 +784,2714 @@ impl Enum {
 fn func(_: Enum::$0) {}
 "#,
         expect![[r#"
-            ta AssocType type AssocType = ()
+            as AssocType type AssocType = ()
+        "#]],
+    );
+}
+
+#[test]
+fn completes_traits_only_after_impl() {
+    check(
+        r#"
+fn foo() -> impl $0 {}
+"#,
+        expect![[r#"
+            ma makro!(…) macro_rules! makro
+            md module
+            tt Trait
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+    check(
+        r#"
+mod m {
+    pub struct S;
+    pub trait Tr {}
+    pub type Alias = S;
+    pub mod nested {}
+}
+fn foo() -> impl m::$0 {}
+"#,
+        expect![[r#"
+            md nested
+            tt Tr
+        "#]],
+    );
+}
+
+#[test]
+fn completes_traits_only_after_dyn() {
+    check(
+        r#"
+fn foo(_: &dyn $0) {}
+"#,
+        expect![[r#"
+            ma makro!(…) macro_rules! makro
+            md module
+            tt Trait
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+    check(
+        r#"
+struct Box<T: ?Sized>(T);
+fn foo(_: Box<dyn $0>) {}
+"#,
+        expect![[r#"
+            ma makro!(…) macro_rules! makro
+            md module
+            tt Trait
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+}
+
+#[test]
+fn completes_self_in_trait() {
+    check(
+        r#"
+trait Foo {
+    fn ret(&self) -> $0;
+}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            sp Self
+            st Record
+            st Tuple
+            st Unit
+            tt Foo
+            tt Trait
+            un Union
+            bt u32
+            kw !
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    check(
+        r#"
+trait Foo {
+    type Assoc;
+    fn ret(&self) -> Self::$0;
+}
+"#,
+        expect![[r#"
+            as Assoc (as Foo) type Assoc
+        "#]],
+    );
+}
+
+#[test]
+fn completes_innermost_self() {
+    check(
+        r#"
+trait Foo {
+    fn foo() {
+        struct Bar;
+        impl Bar {
+            fn ret(&self) -> $0 {}
+        }
+    }
+}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            sp Self
+            st Bar
+            st Record
+            st Tuple
+            st Unit
+            tt Foo
+            tt Trait
+            un Union
+            bt u32
+            it ()
+            kw !
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_consts_only_in_generic_arg() {
+    cov_mark::check!(complete_assoc_const_in_generic_arg);
+    check(
+        r#"
+trait Tr {
+    type Assoc;
+    const C: usize;
+}
+struct Arr<const N: usize>;
+fn foo(_: Arr<Tr::$0>) {}
+"#,
+        expect![[r#"
+            ct C (as Tr)     const C: usize
+            as Assoc (as Tr) type Assoc
+        "#]],
+    );
+    check(
+        r#"
+trait Tr {
+    type Assoc;
+    const C: usize;
+}
+fn foo(_: Tr::$0) {}
+"#,
+        expect![[r#"
+            as Assoc (as Tr) type Assoc
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_consts_of_blanket_impls_in_generic_arg() {
+    check(
+        r#"
+trait A {}
+trait B { const C: usize; }
+impl<T: A> B for T { const C: usize = 0; }
+struct Arr<const N: usize>;
+fn f<T: A>(_: Arr<T::$0>) {}
+"#,
+        expect![[r#"
+            ct C (as B) const C: usize
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_diamond_supertraits_once() {
+    check(
+        r#"
+trait A {
+    type X;
+}
+trait B: A {}
+trait C: A + B {}
+
+fn foo<T>(_: T) where T: C<$0> {}
+"#,
+        expect![[r#"
+            ct CONST
+            en Enum
+            ma makro!(…)   macro_rules! makro
+            md module
+            st Record
+            st Tuple
+            st Unit
+            tt A
+            tt B
+            tt C
+            tt Trait
+            tp T
+            un Union
+            as X =  (as A) type X
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array       [T; N]
+            sn tuple       (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_fully_qualified_trait() {
+    check(
+        r#"
+trait Tr {
+    type Assoc;
+    const CONST: u8;
+    fn func();
+}
+trait Other {
+    type OtherAssoc;
+}
+struct S;
+impl Tr for S {
+    type Assoc = ();
+    const CONST: u8 = 0;
+    fn func() {}
+}
+impl Other for S {
+    type OtherAssoc = ();
+}
+impl S {
+    type Inherent = ();
+}
+fn foo(_: <S as Tr>::$0) {}
+"#,
+        expect![[r#"
+            as Assoc (as Tr) type Assoc
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+struct Vec<T>(T);
+struct IntoIter<T>(T);
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> { None }
+}
+impl<T> IntoIterator for Vec<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> { IntoIter(self.0) }
+}
+fn foo(_: <Vec<u8> as IntoIterator>::$0) {}
+"#,
+        expect![[r#"
+            as IntoIter (as IntoIterator) pub type IntoIter: Iterator<Item = Self::Item>
+            as Item (as IntoIterator) pub type Item
+        "#]],
+    );
+}
+
+#[test]
+fn completes_reexports_of_private_modules_only() {
+    check(
+        r#"
+mod a {
+    mod b {
+        pub struct Hidden;
+    }
+    pub use b::Hidden;
+    struct Private;
+}
+fn foo(_: a::$0) {}
+"#,
+        expect![[r#"
+            st Hidden
+        "#]],
+    );
+    check(
+        r#"
+mod a {
+    mod b {
+        pub struct Hidden;
+    }
+    pub use b::Hidden;
+}
+fn foo(_: a::b::$0) {}
+"#,
+        expect![[""]],
+    );
+}
+
+#[test]
+fn completes_builtins_reexported_from_primitive_module() {
+    // `check_edit` requires exactly one matching item, so this also makes sure the builtins are
+    // not emitted twice.
+    check_edit(
+        "u32",
+        r#"
+//- /main.rs crate:main deps:std
+fn foo(_: std::primitive::u$0) {}
+//- /std.rs crate:std
+pub mod primitive {
+    pub use bool;
+    pub use str;
+    pub use u32;
+}
+"#,
+        r#"
+fn foo(_: std::primitive::u32) {}
+"#,
+    );
+    check_edit(
+        "str",
+        r#"
+//- /main.rs crate:main deps:std
+fn foo(_: &std::primitive::$0) {}
+//- /std.rs crate:std
+pub mod primitive {
+    pub use bool;
+    pub use str;
+    pub use u32;
+}
+"#,
+        r#"
+fn foo(_: &std::primitive::str) {}
+"#,
+    );
+}
+
+#[test]
+fn distinguishes_assoc_types_from_type_aliases() {
+    check(
+        r#"
+trait Trait {
+    type Assoc;
+    fn f(_: Self::$0);
+}
+"#,
+        expect![[r#"
+            as Assoc (as Trait) type Assoc
+        "#]],
+    );
+    check(
+        r#"
+mod m {
+    pub type Alias = u32;
+}
+fn f(_: m::$0) {}
+"#,
+        expect![[r#"
+            ta Alias = u32
+        "#]],
+    );
+}
+
+#[test]
+fn completes_lifetimes_in_lifetime_generic_arg_position() {
+    cov_mark::check!(complete_lifetime_in_generic_arg);
+    check(
+        r#"
+struct Ref<'a, T>(&'a T);
+fn f<'b>() -> Ref<$0> {}
+"#,
+        expect![[r#"
+            ct CONST
+            en Enum
+            lt 'b
+            lt 'static
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Ref<…>
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn no_lifetimes_in_type_generic_arg_position() {
+    check(
+        r#"
+struct Ref<'a, T>(&'a T);
+fn f<'b>() -> Ref<'b, $0> {}
+"#,
+        expect![[r#"
+            ct CONST
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Ref<…>
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_in_assoc_type_binding_of_impl_trait() {
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+struct S;
+fn f() -> impl Iterator<Item = $0> {}
+"#,
+        expect![[r#"
+            en Option<…>
+            md core
+            st S
+            tt IntoIterator
+            tt Iterator
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array        [T; N]
+            sn tuple        (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+struct S;
+fn f(_: &dyn Iterator<Item = $0>) {}
+"#,
+        expect![[r#"
+            en Option<…>
+            md core
+            st S
+            tt IntoIterator
+            tt Iterator
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array        [T; N]
+            sn tuple        (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn no_self_or_implemented_trait_in_impl_header() {
+    check(
+        r#"
+trait Clone {}
+impl Clone for $0 {}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    check(
+        r#"
+trait Clone {}
+struct S<T>(T);
+impl Clone for S<$0> {}
+"#,
+        expect![[r#"
+            ct CONST
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st S<…>
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    check(
+        r#"
+trait Clone {}
+impl Clone for Record {
+    fn f(_: $0) {}
+}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            sp Self
+            st Record
+            st Tuple
+            st Unit
+            tt Clone
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn module_items_are_filtered_by_typed_prefix() {
+    let items: String = (0..5000).map(|i| format!("    pub struct Item{};\n", i)).collect();
+    let fixture = |path: &str| {
+        format!("mod big {{\n{}    pub struct Needle;\n}}\nfn f(_: {}) {{}}\n", items, path)
+    };
+
+    let all = get_all_items(TEST_CONFIG, &fixture("big::$0"), None);
+    assert_eq!(all.len(), 5001);
+
+    let filtered = get_all_items(TEST_CONFIG, &fixture("big::nedl$0"), None);
+    let labels: Vec<_> = filtered.iter().map(|it| it.label()).collect();
+    assert_eq!(labels, ["Needle"]);
+}
+
+#[test]
+fn completes_assoc_types_of_blanket_impls_on_type_params() {
+    cov_mark::check!(complete_assoc_type_of_blanket_impl);
+    check(
+        r#"
+trait A { type AssocA; }
+trait B { type AssocB; }
+impl<T: A> B for T { type AssocB = (); }
+fn f<T: A>() -> T::$0
+"#,
+        expect![[r#"
+            as AssocA (as A) type AssocA
+            as AssocB (as B) type AssocB
+        "#]],
+    );
+}
+
+#[test]
+fn no_assoc_types_of_blanket_impls_for_traits_not_in_scope() {
+    check(
+        r#"
+trait A { type AssocA; }
+mod m {
+    pub trait B { type AssocB; }
+    impl<T: super::A> B for T { type AssocB = (); }
+}
+fn f<T: A>() -> T::$0
+"#,
+        expect![[r#"
+            as AssocA (as A) type AssocA
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_into_iterator_bound() {
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+fn f<T: IntoIterator>() -> T::$0
+"#,
+        expect![[r#"
+            as IntoIter (as IntoIterator) pub type IntoIter: Iterator<Item = Self::Item>
+            as Item (as IntoIterator) pub type Item
+        "#]],
+    );
+}
+
+#[test]
+fn completes_never_type_in_return_position_only() {
+    check(
+        r#"
+fn f() -> $0 { loop {} }
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw !
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    check(
+        r#"
+fn f() { let x: $0 }
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…) macro_rules! makro
+            md module
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_tuple_and_array_type_snippets() {
+    check_edit(
+        "tuple",
+        r#"
+fn f(_: $0) {}
+"#,
+        r#"
+fn f(_: (${1:T}, ${2:U})) {}
+"#,
+    );
+    check_edit(
+        "array",
+        r#"
+fn f(_: $0) {}
+"#,
+        r#"
+fn f(_: [${1:T}; ${2:N}]) {}
+"#,
+    );
+}
+
+#[test]
+fn no_type_snippets_without_snippet_cap() {
+    let config = CompletionConfig { snippet_cap: None, ..TEST_CONFIG };
+    let items = get_all_items(config, "fn f(_: $0) {}", None);
+    assert!(!items.iter().any(|it| it.kind() == CompletionItemKind::Snippet));
+}
+
+#[test]
+fn no_doc_hidden_assoc_items_of_dependencies() {
+    let dep = r#"
+//- /dep.rs crate:dep
+pub trait Tr {
+    #[doc(hidden)]
+    type Internal;
+    type Public;
+    #[doc(hidden)]
+    const INTERNAL: usize;
+    const PUBLIC: usize;
+}
+"#;
+    let check = |main: &str, expect: Expect| {
+        check_no_base_items(&format!("//- /main.rs crate:main deps:dep\n{}{}", main, dep), expect)
+    };
+    check(
+        "use dep::Tr; fn f<T: Tr>() -> T::$0 {}",
+        expect![[r#"
+        as Public (as Tr) pub type Public
+    "#]],
+    );
+    check(
+        "fn f<T: dep::Tr>() -> T::$0",
+        expect![[r#"
+        as Public (as Tr) pub type Public
+    "#]],
+    );
+    check(
+        "fn f<T: dep::Tr>() -> <T as dep::Tr>::$0 {}",
+        expect![[r#"
+        as Public (as Tr) pub type Public
+    "#]],
+    );
+    check(
+        "fn f<T: dep::Tr>() -> dep::Tr::$0 {}",
+        expect![[r#"
+        as Public (as Tr) pub type Public
+    "#]],
+    );
+    check(
+        "fn f(_: impl dep::Tr<$0>) {}",
+        expect![[r#"
+            md dep
+            as Public =  (as Tr) pub type Public
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array             [T; N]
+            sn tuple             (T, U)
+        "#]],
+    );
+    check(
+        "struct S<const N: usize>; fn f<T: dep::Tr>(_: S<{ T::$0 }>) {}",
+        expect![[r#"
+        ct PUBLIC (as Tr) pub const PUBLIC: usize
+        as Public (as Tr) pub type Public
+    "#]],
+    );
+}
+
+#[test]
+fn completes_doc_hidden_assoc_items_of_current_crate() {
+    check(
+        r#"
+trait Tr {
+    #[doc(hidden)]
+    type Internal;
+}
+fn f<T: Tr>() -> T::$0 {}
+"#,
+        expect![[r#"
+            as Internal (as Tr) type Internal
+        "#]],
+    );
+}
+
+#[test]
+fn shows_aliased_type_in_type_alias_detail() {
+    check_no_base_items(
+        r#"
+struct Bar<T>(T);
+type Foo = Bar<u32>;
+type Generic<T> = Bar<T>;
+type Long = (Bar<u32>, Bar<u32>, Bar<u32>, Bar<u32>, Bar<u32>, Bar<u32>);
+type Unresolved = Baz;
+fn f(_: $0) {}
+"#,
+        expect![[r#"
+            st Bar<…>
+            ta Foo        = Bar<u32>
+            ta Generic<…> = Bar<T>
+            ta Long       = (Bar<u32>, Bar<u32>, Bar<u32>, …)
+            ta Unresolved = Baz
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array      [T; N]
+            sn tuple      (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn inferred_type_wrapped_in_smart_pointers_in_scope() {
+    const FIXTURE: &str = r#"
+struct Box<T>(T);
+mod rc {
+    pub struct Rc<T>(T);
+}
+use rc::Rc;
+struct S;
+fn f() {
+    let x: $0 = S;
+}
+"#;
+    let inferred = |config| {
+        get_all_items(config, FIXTURE, None)
+            .into_iter()
+            .filter(|it| it.kind() == CompletionItemKind::InferredType)
+            .map(|it| (it.label().to_owned(), it.relevance().score()))
+            .collect::<Vec<_>>()
+    };
+    let config = CompletionConfig { enable_smart_pointer_inferred_types: true, ..TEST_CONFIG };
+
+    let items = inferred(config.clone());
+    let labels: Vec<_> = items.iter().map(|(label, _)| &**label).collect();
+    assert_eq!(labels, ["Box<S>", "Rc<S>", "S"]);
+    assert!(items.iter().all(|(label, score)| label == "S" || *score < items[2].1));
+
+    assert_eq!(inferred(CompletionConfig { snippet_cap: None, ..config }).len(), 1);
+    assert_eq!(inferred(TEST_CONFIG).len(), 1);
+
+    check_edit_with_config(
+        CompletionConfig { enable_smart_pointer_inferred_types: true, ..TEST_CONFIG },
+        "Rc<S>",
+        FIXTURE,
+        r#"
+struct Box<T>(T);
+mod rc {
+    pub struct Rc<T>(T);
+}
+use rc::Rc;
+struct S;
+fn f() {
+    let x: Rc<${0:S}> = S;
+}
+"#,
+    );
+}
+
+#[test]
+fn no_enum_variants_in_type_position() {
+    cov_mark::check!(no_enum_variants_in_type_position);
+    check(
+        r#"
+enum E { A, B }
+fn f(_: E::$0) {}
+"#,
+        expect![[""]],
+    );
+}
+
+#[test]
+fn completes_fn_trait_sugar_in_bounds() {
+    cov_mark::check!(complete_fn_trait_sugar);
+    check_edit(
+        "FnOnce(..) -> ..",
+        r#"
+//- minicore: fn
+use core::ops::FnOnce;
+fn f(x: impl $0) {}
+"#,
+        r#"
+use core::ops::FnOnce;
+fn f(x: impl FnOnce(${1}) -> ${2}) {}
+"#,
+    );
+    check_edit(
+        "Fn(..) -> ..",
+        r#"
+//- minicore: fn
+use core::ops::Fn;
+fn f<F>(x: F) where F: $0 {}
+"#,
+        r#"
+use core::ops::Fn;
+fn f<F>(x: F) where F: Fn(${1}) -> ${2} {}
+"#,
+    );
+    check_no_base_items(
+        r#"
+//- minicore: fn
+use core::ops::{Fn, FnMut, FnOnce};
+fn f<F>(x: F) where F: $0 {}
+"#,
+        expect![[r#"
+            md core
+            tt Fn
+            tt FnMut
+            tt FnOnce
+            kw ?Sized
+            kw crate::
+            kw self::
+            kw super::
+            sn Fn(..) -> ..
+            sn FnMut(..) -> ..
+            sn FnOnce(..) -> ..
+            sn for<'a>
+        "#]],
+    );
+}
+
+#[test]
+fn no_fn_trait_sugar_without_snippet_cap() {
+    let items = get_all_items(
+        CompletionConfig { snippet_cap: None, ..TEST_CONFIG },
+        r#"
+//- minicore: fn
+use core::ops::Fn;
+fn f(x: impl $0) {}
+"#,
+        None,
+    );
+    assert!(items.iter().any(|it| it.label() == "Fn"));
+    assert!(!items.iter().any(|it| it.label().starts_with("Fn(")));
+}
+
+#[test]
+fn completes_dyn_keyword_depending_on_edition() {
+    let has_dyn = |ra_fixture| {
+        get_all_items(TEST_CONFIG, ra_fixture, None)
+            .iter()
+            .any(|it| it.kind() == CompletionItemKind::Keyword && it.label() == "dyn")
+    };
+    assert!(!has_dyn(
+        r#"
+//- /lib.rs crate:main edition:2015
+trait Trait {}
+fn f(_: &$0) {}
+"#
+    ));
+    assert!(has_dyn(
+        r#"
+//- /lib.rs crate:main edition:2018
+trait Trait {}
+fn f(_: &$0) {}
+"#
+    ));
+    assert!(has_dyn(
+        r#"
+//- /lib.rs crate:main edition:2021
+trait Trait {}
+fn f(_: &$0) {}
+"#
+    ));
+    // Trait objects can't be spelled out in bounds.
+    assert!(!has_dyn(
+        r#"
+//- /lib.rs crate:main edition:2021
+trait Trait {}
+fn f(_: impl $0) {}
+"#
+    ));
+}
+
+#[test]
+fn type_position_completions_api() {
+    let labels = |ra_fixture| {
+        let (db, position) = position(ra_fixture);
+        let items = completions_in_type_position(&db, &TEST_CONFIG, position);
+        let mut labels = items.iter().map(|it| it.label().to_owned()).collect::<Vec<_>>();
+        labels.sort();
+        labels
+    };
+    expect![[r#"
+        [
+            "Foo",
+            "Trait",
+            "array",
+            "bool",
+            "char",
+            "crate::",
+            "dyn",
+            "f32",
+            "f64",
+            "i128",
+            "i16",
+            "i32",
+            "i64",
+            "i8",
+            "isize",
+            "self::",
+            "str",
+            "super::",
+            "tuple",
+            "u128",
+            "u16",
+            "u32",
+            "u64",
+            "u8",
+            "usize",
+        ]
+    "#]]
+    .assert_debug_eq(&labels(
+        r#"
+struct Foo;
+trait Trait {}
+fn bar() {}
+fn f(_: $0) {}
+"#,
+    ));
+    let labels = labels(
+        r#"
+struct Foo;
+fn f() {
+    let _ = $0;
+}
+"#,
+    );
+    assert!(labels.is_empty(), "{:?}", labels);
+}
+
+#[test]
+fn completes_self_assoc_const_in_array_length() {
+    // Array lengths are expressions, so these come from the expression path completions.
+    check(
+        r#"
+struct Foo;
+impl Foo {
+    const LEN: usize = 2;
+    type Out = u8;
+    fn f() -> [u8; Self::$0] {}
+}
+"#,
+        expect![[r#"
+            ct LEN const LEN: usize
+            fn f() fn() -> [u8; _]
+        "#]],
+    );
+}
+
+#[test]
+fn shows_generic_param_defaults_in_detail() {
+    check(
+        r#"
+struct Defaulted<'a, T, U = u32, const N: usize>(&'a T, U);
+fn f(_: Defa$0) {}
+"#,
+        expect![[r#"
+            en Enum
+            ma makro!(…)    macro_rules! makro
+            md module
+            st Defaulted<…> <'a, T, U = u32, const N: usize>
+            st Record
+            st Tuple
+            st Unit
+            tt Trait
+            un Union
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array        [T; N]
+            sn tuple        (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_raw_identifiers_with_prefix() {
+    check_edit(
+        "match",
+        r#"
+struct r#match;
+fn f(_: $0) {}
+"#,
+        r#"
+struct r#match;
+fn f(_: r#match) {}
+"#,
+    );
+    check_edit(
+        "type",
+        r#"
+struct r#type<T>(T);
+fn f(_: $0) {}
+"#,
+        r#"
+struct r#type<T>(T);
+fn f(_: r#type<$0>) {}
+"#,
+    );
+    // `async` is only a keyword since the 2018 edition.
+    check_edit(
+        "async",
+        r#"
+//- /lib.rs crate:main edition:2018
+struct r#async;
+fn f(_: $0) {}
+"#,
+        r#"
+struct r#async;
+fn f(_: r#async) {}
+"#,
+    );
+    check_edit(
+        "async",
+        r#"
+//- /lib.rs crate:main edition:2015
+struct r#async;
+fn f(_: $0) {}
+"#,
+        r#"
+struct r#async;
+fn f(_: async) {}
+"#,
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_assoc_type_bounds() {
+    cov_mark::check!(complete_assoc_type_of_projection);
+    check_no_base_items(
+        r#"
+trait A { type X: B; }
+trait Super { type Z; }
+trait B: Super { type Y; }
+fn f<T: A>() -> T::X::$0 {}
+"#,
+        expect![[r#"
+            as Y (as B)     type Y
+            as Z (as Super) type Z
+        "#]],
+    );
+}
+
+#[test]
+fn inserts_angle_brackets_only_when_needed() {
+    check_edit(
+        "HashMap",
+        r#"
+struct HashMap<K, V, S = ()>(K, V, S);
+fn f(_: Hash$0) {}
+"#,
+        r#"
+struct HashMap<K, V, S = ()>(K, V, S);
+fn f(_: HashMap<$0>) {}
+"#,
+    );
+    check_edit(
+        "Unit",
+        r#"
+struct Unit;
+fn f(_: Un$0) {}
+"#,
+        r#"
+struct Unit;
+fn f(_: Unit) {}
+"#,
+    );
+    // The brackets have been typed already.
+    check_edit(
+        "Vec",
+        r#"
+struct Vec<T>(T);
+fn f(_: Ve$0<) {}
+"#,
+        r#"
+struct Vec<T>(T);
+fn f(_: Vec<) {}
+"#,
+    );
+    check_edit_with_config(
+        CompletionConfig { snippet_cap: None, ..TEST_CONFIG },
+        "Vec",
+        r#"
+struct Vec<T>(T);
+fn f(_: Ve$0) {}
+"#,
+        r#"
+struct Vec<T>(T);
+fn f(_: Vec) {}
+"#,
+    );
+}
+
+#[test]
+fn completes_sized_relaxation_in_type_param_bounds() {
+    let has_relaxation = |ra_fixture| {
+        get_all_items(TEST_CONFIG, ra_fixture, None).iter().any(|it| it.label() == "?Sized")
+    };
+    assert!(has_relaxation("fn f<T: $0>() {}"));
+    assert!(has_relaxation("trait Trait {} fn f<T>() where T: Trait + $0 {}"));
+    assert!(has_relaxation("trait Trait { type Assoc: $0; }"));
+    assert!(!has_relaxation("fn f(_: impl $0) {}"));
+    assert!(!has_relaxation("fn f(_: &dyn $0) {}"));
+    assert!(!has_relaxation("trait Trait: $0 {}"));
+    assert!(!has_relaxation("fn f<'a>() where 'a: $0 {}"));
+    assert!(!has_relaxation("fn f(_: $0) {}"));
+}
+
+#[test]
+fn glob_imported_types_are_listed_once() {
+    check(
+        r#"
+mod other { pub struct MyStruct; }
+mod inner {
+    use super::other::*;
+    use super::other::MyStruct;
+    fn f(_: $0) {}
+}
+"#,
+        expect![[r#"
+            ma makro!(…) macro_rules! makro
+            st MyStruct
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array     [T; N]
+            sn tuple     (T, U)
+        "#]],
+    );
+    // Renamed imports are listed under each of their names.
+    check_no_base_items(
+        r#"
+mod other { pub struct LongName; }
+use other::LongName;
+use other::LongName as L;
+fn f(_: LongN$0) {}
+"#,
+        expect![[r#"
+            md other
+            st L
+            st LongName
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array    [T; N]
+            sn tuple    (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_impl_trait_param_bounds() {
+    // Opaque parameter types can't be named, so their associated types are only reachable through
+    // bindings on the bound itself or by projecting through the trait.
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+fn f(x: impl IntoIterator<$0>) {}
+"#,
+        expect![[r#"
+            en Option<…>
+            md core
+            tt IntoIterator
+            tt Iterator
+            as IntoIter =  (as IntoIterator) pub type IntoIter: Iterator<Item = Self::Item>
+            as Item =  (as IntoIterator) pub type Item
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array                  [T; N]
+            sn tuple                  (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+fn f(x: impl IntoIterator) {
+    let _: <_ as IntoIterator>::$0;
+}
+"#,
+        expect![[r#"
+            as IntoIter (as IntoIterator) pub type IntoIter: Iterator<Item = Self::Item>
+            as Item (as IntoIterator) pub type Item
+        "#]],
+    );
+}
+
+#[test]
+fn completes_super_chains_in_nested_modules() {
+    check_no_base_items(
+        r#"
+mod a {
+    mod b {
+        mod c {
+            fn f(_: $0) {}
+        }
+    }
+}
+"#,
+        expect![[r#"
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            kw super::super::
+            kw super::super::super::
+            sn array                 [T; N]
+            sn tuple                 (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn super_chains_skip_block_modules() {
+    check_no_base_items(
+        r#"
+mod a {
+    mod b {
+        fn f() {
+            struct Local;
+            let _: $0;
+        }
+    }
+}
+"#,
+        expect![[r#"
+            st Local
+            bt u32
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            kw super::super::
+            sn array          [T; N]
+            sn tuple          (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_auto_traits_in_negative_impls() {
+    cov_mark::check!(complete_negative_trait);
+    check_no_base_items(
+        r#"
+mod module {}
+auto trait Send {}
+trait Tr {}
+struct Foo;
+impl !$0 for Foo {}
+"#,
+        expect![[r#"
+            md module
+            tt Send
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_in_qualified_path_start() {
+    check_no_base_items(
+        r#"
+struct Foo;
+trait Tr {}
+fn f() { let x = <$0; }
+"#,
+        expect![[r#"
+            st Foo
+            tt Tr
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+    // Generic arguments of a preceding path are not a qualified path.
+    check_no_base_items(
+        r#"
+struct Foo;
+const C: usize = 0;
+struct Arr<const N: usize>;
+fn f() { let x = Arr::<$0; }
+"#,
+        expect![[r#"
+            ct C
+            st Arr
+            st Foo
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array       [T; N]
+            sn const { … }
+            sn tuple       (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn only_object_safe_traits_after_dyn() {
+    let fixture = |bound: &str| {
+        format!(
+            r#"
+//- minicore: sized
+mod fake {{ pub trait Sized {{}} }}
+trait Generic {{ fn generic<T>(&self, t: T); }}
+trait Constant {{ const C: u32; }}
+trait Sub: Generic {{}}
+trait OptOut {{ fn generic<T>(&self, t: T) where Self: Sized; }}
+trait OptOutPath {{ fn generic<T>(&self, t: T) where Self: core::marker::Sized; }}
+trait FakeOptOut {{ fn generic<T>(&self, t: T) where Self: fake::Sized; }}
+trait Safe {{ fn method(&self); }}
+fn f(_: {bound}) {{}}
+"#
+        )
+    };
+    {
+        cov_mark::check!(skip_non_object_safe_trait);
+        check_no_base_items(
+            &fixture("&dyn $0"),
+            expect![[r#"
+                md core
+                md fake
+                tt OptOut
+                tt OptOutPath
+                tt Safe
+                tt Sized
+                kw crate::
+                kw self::
+                kw super::
+                sn for<'a>
+            "#]],
+        );
+    }
+    check_no_base_items(
+        &fixture("&dyn Safe + $0"),
+        expect![[r#"
+            lt 'static
+            md core
+            md fake
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+    check_no_base_items(
+        &fixture("impl $0"),
+        expect![[r#"
+            md core
+            md fake
+            tt Constant
+            tt FakeOptOut
+            tt Generic
+            tt OptOut
+            tt OptOutPath
+            tt Safe
+            tt Sized
+            tt Sub
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_multiple_bounds() {
+    // Same-named associated types of different traits are both listed.
+    check_no_base_items(
+        r#"
+trait A { type X; type Y; }
+trait B { type X; type Z; }
+fn f<T: A + B>() -> T::$0 {}
+"#,
+        expect![[r#"
+            as X (as A) type X
+            as X (as B) type X
+            as Y (as A) type Y
+            as Z (as B) type Z
+        "#]],
+    );
+    // An associated type reachable through several bounds is only listed once.
+    check_no_base_items(
+        r#"
+trait S { type X; }
+trait A: S {}
+trait B: S {}
+fn f<T: A + B>() -> T::$0 {}
+"#,
+        expect![[r#"
+            as X (as S) type X
+        "#]],
+    );
+}
+
+#[test]
+fn completes_lifetimes_in_ref_types() {
+    {
+        cov_mark::check!(complete_lifetime_in_ref_type);
+        check_no_base_items(
+            r#"
+struct Foo;
+fn f<'a>(x: &$0 str) {}
+"#,
+            expect![[r#"
+                lt 'a
+                lt 'static
+                st Foo
+                bt u32
+                kw crate::
+                kw dyn
+                kw self::
+                kw super::
+                sn array   [T; N]
+                sn tuple   (T, U)
+            "#]],
+        );
+    }
+    check_edit(
+        "'a",
+        r#"
+fn f<'a>(x: &$0 str) {}
+"#,
+        r#"
+fn f<'a>(x: &'a str) {}
+"#,
+    );
+    // The lifetime of a mutable reference goes before the `mut`.
+    check_no_base_items(
+        r#"
+struct Foo;
+fn f<'a>(x: &mut $0) {}
+"#,
+        expect![[r#"
+            st Foo
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_qualified_assoc_const_in_array_length() {
+    // Array lengths are expressions, so the usual value completions of the qualifier apply.
+    check_no_base_items(
+        r#"
+enum MyEnum { A, B }
+impl MyEnum { const N: usize = 3; }
+struct S { f: [u8; MyEnum::$0] }
+"#,
+        expect![[r#"
+            ct N const N: usize
+            ev A A
+            ev B B
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_in_ty_fragment_of_macro_call() {
+    // The macro call is expanded, so a `$t:ty` fragment is completed as a type position.
+    check_no_base_items(
+        r#"
+macro_rules! m { ($t:ty) => { fn f(_: $t) {} } }
+struct Foo;
+m!(Fo$0);
+"#,
+        expect![[r#"
+            ma m!(…)   macro_rules! m
+            st Foo
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_through_dyn_trait_alias() {
+    cov_mark::check!(complete_assoc_item_of_dyn_trait);
+    check_no_base_items(
+        r#"
+trait Super { type Output; }
+trait MyTrait: Super { type Item; fn f(); }
+type Obj = dyn MyTrait<Item = u32, Output = u32>;
+fn f(_: Obj::$0) {}
+"#,
+        expect![[r#"
+            as Item (as MyTrait) type Item
+            as Output (as Super) type Output
+        "#]],
+    );
+}
+
+#[test]
+fn completes_private_items_in_crate_if_enabled() {
+    const FIXTURE: &str = r#"
+mod a {
+    struct VPrivate;
+    pub(crate) struct Visible;
+    mod inner {
+        pub(crate) trait VInner {}
+    }
+    pub(crate) mod open {
+        pub(crate) trait VOpen {}
+    }
+}
+mod b {
+    fn f(_: V$0) {}
+}
+"#;
+    let imported_items = |config| {
+        get_all_items(config, FIXTURE, None)
+            .into_iter()
+            .filter(|it| it.label().contains("(use "))
+            .map(|it| it.label().to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert!(imported_items(TEST_CONFIG).is_empty());
+
+    let config = CompletionConfig { enable_private_items_in_crate: true, ..TEST_CONFIG };
+    {
+        cov_mark::check!(complete_private_item_in_crate);
+        assert_eq!(
+            imported_items(config.clone()),
+            ["Visible (use crate::a::Visible)", "VOpen (use crate::a::open::VOpen)"]
+        );
+    }
+    check_edit_with_config(
+        config,
+        "Visible",
+        FIXTURE,
+        r#"
+mod a {
+    struct VPrivate;
+    pub(crate) struct Visible;
+    mod inner {
+        pub(crate) trait VInner {}
+    }
+    pub(crate) mod open {
+        pub(crate) trait VOpen {}
+    }
+}
+mod b {
+    use crate::a::Visible;
+
+    fn f(_: Visible) {}
+}
+"#,
+    );
+}
+
+#[test]
+fn completes_hrtb_snippet_at_bound_start() {
+    {
+        cov_mark::check!(complete_hrtb_snippet);
+        check_edit(
+            "for<'a>",
+            r#"
+fn f<F>() where F: $0 {}
+"#,
+            r#"
+fn f<F>() where F: for<${1:'a}> $0 {}
+"#,
+        );
+    }
+    check_edit(
+        "for<'a>",
+        r#"
+fn f<F: Copy + $0>() {}
+"#,
+        r#"
+fn f<F: Copy + for<${1:'a}> $0>() {}
+"#,
+    );
+    let snippets = |ra_fixture| {
+        get_all_items(TEST_CONFIG, ra_fixture, None)
+            .into_iter()
+            .filter(|it| it.kind() == CompletionItemKind::Snippet)
+            .count()
+    };
+    assert_eq!(snippets("fn f<F>() where F: ?$0 {}"), 0);
+    assert_eq!(snippets("mod m {} fn f<F>() where F: m::$0 {}"), 0);
+    let no_snippets = CompletionConfig { snippet_cap: None, ..TEST_CONFIG };
+    assert!(get_all_items(no_snippets, "fn f<F>() where F: $0 {}", None)
+        .iter()
+        .all(|it| it.label() != "for<'a>"));
+}
+
+#[test]
+fn inherent_and_trait_assoc_types_of_adt_are_listed_once() {
+    check_no_base_items(
+        r#"
+struct S;
+impl S { type X = u32; }
+trait Tr { type X; }
+impl Tr for S { type X = u32; }
+fn f(_: S::$0) {}
+"#,
+        expect![[r#"
+            as X type X = u32
+        "#]],
+    );
+}
+
+#[test]
+fn completes_receiver_types_of_self_param() {
+    let receivers = |ra_fixture| {
+        get_all_items(TEST_CONFIG, ra_fixture, None)
+            .into_iter()
+            .filter(|it| it.kind() == CompletionItemKind::Snippet)
+            .map(|it| it.label().to_owned())
+            .collect::<Vec<_>>()
+    };
+    let expected = ["Self", "&Self", "&mut Self"];
+    assert_eq!(receivers("trait Tr { fn f(self: $0); }"), expected);
+    assert_eq!(receivers("struct S; impl S { fn f(mut self: $0) {} }"), expected);
+    // Smart pointers are only offered if they are in scope.
+    assert_eq!(
+        receivers(
+            r#"
+mod pin { pub struct Pin<P>(P); }
+use pin::Pin;
+struct Box<T>(T);
+struct Rc<T>(T);
+trait Tr { fn f(self: $0); }
+"#
+        ),
+        ["Self", "&Self", "&mut Self", "Box<Self>", "Pin<&mut Self>", "Rc<Self>"]
+    );
+    check_edit(
+        "Pin<&mut Self>",
+        r#"
+mod pin { pub struct Pin<P>(P); }
+use pin::Pin;
+trait Tr { fn poll(self: $0); }
+"#,
+        r#"
+mod pin { pub struct Pin<P>(P); }
+use pin::Pin;
+trait Tr { fn poll(self: Pin<&mut Self>); }
+"#,
+    );
+    // Ordinary parameters don't get receiver types.
+    assert_eq!(receivers("trait Tr { fn f(x: $0); }"), ["tuple", "array"]);
+}
+
+#[test]
+fn completes_generic_params_in_where_pred_subject() {
+    check_no_base_items(
+        r#"
+struct Foo;
+fn f<T>() where $0 {}
+"#,
+        expect![[r#"
+            st Foo
+            tp T
+            bt u32
+            kw crate::
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+struct S<T>(T) where $0;
+"#,
+        expect![[r#"
+            sp Self
+            st S<…>
+            tp T
+            bt u32
+            kw crate::
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn excluded_crates_are_not_completed() {
+    const FIXTURE: &str = r#"
+//- /main.rs crate:main deps:dep,other
+use dep::DepStruct;
+use other::OtherStruct;
+struct Local;
+fn f(_: $0) {}
+//- /dep.rs crate:dep
+pub struct DepStruct;
+//- /other.rs crate:other
+pub struct OtherStruct;
+"#;
+    let labels = |config| {
+        get_all_items(config, FIXTURE, None)
+            .into_iter()
+            .filter(|it| it.kind() != CompletionItemKind::Keyword)
+            .filter(|it| it.kind() != CompletionItemKind::Snippet)
+            .filter(|it| it.kind() != CompletionItemKind::BuiltinType)
+            .map(|it| it.label().to_owned())
+            .sorted()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(labels(TEST_CONFIG), ["DepStruct", "Local", "OtherStruct", "dep", "other"]);
+    let config =
+        CompletionConfig { excluded_crates: vec![CrateName::new("dep").unwrap()], ..TEST_CONFIG };
+    assert_eq!(labels(config), ["Local", "OtherStruct", "other"]);
+}
+
+#[test]
+fn completes_gat_with_arg_placeholders() {
+    cov_mark::check!(inserts_gat_args);
+    check_edit(
+        "Item (as LendingIter)",
+        r#"
+trait LendingIter { type Item<'a, T>; type Plain; }
+fn f<I: LendingIter>() -> I::$0 {}
+"#,
+        r#"
+trait LendingIter { type Item<'a, T>; type Plain; }
+fn f<I: LendingIter>() -> I::Item<${1:'a}, ${2:T}> {}
+"#,
+    );
+    check_edit(
+        "Plain (as LendingIter)",
+        r#"
+trait LendingIter { type Item<'a, T>; type Plain; }
+fn f<I: LendingIter>() -> I::$0 {}
+"#,
+        r#"
+trait LendingIter { type Item<'a, T>; type Plain; }
+fn f<I: LendingIter>() -> I::Plain {}
+"#,
+    );
+    check_edit_with_config(
+        CompletionConfig { snippet_cap: None, ..TEST_CONFIG },
+        "Item (as LendingIter)",
+        r#"
+trait LendingIter { type Item<'a>; }
+fn f<I: LendingIter>() -> I::$0 {}
+"#,
+        r#"
+trait LendingIter { type Item<'a>; }
+fn f<I: LendingIter>() -> I::Item {}
+"#,
+    );
+}
+
+#[test]
+fn completes_inline_const_in_const_generic_arg() {
+    const FIXTURE: &str = r#"
+struct Arr<T, const N: usize>(T);
+const LEN: usize = 3;
+fn f(_: Arr<u8, $0>) {}
+"#;
+    {
+        cov_mark::check!(complete_inline_const_in_generic_arg);
+        check_edit(
+            "const",
+            FIXTURE,
+            r#"
+struct Arr<T, const N: usize>(T);
+const LEN: usize = 3;
+fn f(_: Arr<u8, const { $0 }>) {}
+"#,
+        );
+    }
+    let items = get_all_items(TEST_CONFIG, FIXTURE, None);
+    let score = |label| items.iter().find(|it| it.label() == label).unwrap().relevance().score();
+    assert!(score("LEN") > score("const { … }"));
+    // Not offered for type parameters.
+    let items =
+        get_all_items(TEST_CONFIG, "struct Arr<T, const N: usize>(T);\nfn f(_: Arr<$0>) {}", None);
+    assert!(items.iter().all(|it| it.lookup() != "const"));
+}
+
+#[test]
+fn completes_snake_case_modules_by_camel_case_prefix() {
+    const FIXTURE: &str = r#"
+mod my_module { pub struct S; }
+mod other { pub mod my_module { pub struct S; } }
+fn f(_: MyMod$0) {}
+"#;
+    {
+        cov_mark::check!(complete_module_by_camel_case);
+        check_edit(
+            "MyModule",
+            FIXTURE,
+            r#"
+mod my_module { pub struct S; }
+mod other { pub mod my_module { pub struct S; } }
+fn f(_: my_module) {}
+"#,
+        );
+    }
+    check_edit(
+        "MyModule",
+        r#"
+mod my_module { pub struct S; }
+mod other { pub mod my_module { pub struct S; } }
+fn f(_: other::MyMod$0) {}
+"#,
+        r#"
+mod my_module { pub struct S; }
+mod other { pub mod my_module { pub struct S; } }
+fn f(_: other::my_module) {}
+"#,
+    );
+    // Snake case prefixes keep matching the module under its own name.
+    let items = get_all_items(TEST_CONFIG, "mod my_module {}\nfn f(_: my_mo$0) {}", None);
+    assert!(items.iter().any(|it| it.label() == "my_module" && it.lookup() == "my_module"));
+    // Prefixes that don't transliterate to the module's name are left to the client.
+    let items = get_all_items(TEST_CONFIG, "mod my_module {}\nfn f(_: MyOth$0) {}", None);
+    assert!(items.iter().all(|it| it.lookup() != "MyModule"));
+}
+
+#[test]
+fn completes_inferred_type_placeholder_in_bodies() {
+    let has_placeholder = |ra_fixture: &str| {
+        get_all_items(TEST_CONFIG, ra_fixture, None).iter().any(|it| it.label() == "_")
+    };
+    {
+        cov_mark::check!(complete_inferred_type_placeholder);
+        assert!(has_placeholder("struct Vec<T>(T);\nfn f() { let x: $0 = 0; }"));
+    }
+    assert!(has_placeholder("struct Vec<T>(T);\nfn f() { let x: Vec<$0> = Vec(0); }"));
+    assert!(has_placeholder("fn g<T>() {}\nfn f() { g::<$0>(); }"));
+    assert!(!has_placeholder("struct Vec<T>(T);\nfn f(x: $0) {}"));
+    assert!(!has_placeholder("struct Vec<T>(T);\nfn f(x: Vec<$0>) {}"));
+    assert!(!has_placeholder("struct Vec<T>(T);\nfn f() { fn g(x: Vec<$0>) {} }"));
+    assert!(!has_placeholder("struct Arr<const N: usize>;\nfn f() { let x: Arr<$0>; }"));
+}
+
+#[test]
+fn completes_items_of_projection_qualifiers() {
+    {
+        cov_mark::check!(projection_bounds_without_items);
+        check_no_base_items(
+            r#"
+//- minicore: sized
+trait A { type X: Sized; }
+fn f<T: A>() -> T::X::$0 {}
+"#,
+            expect![[""]],
+        );
+    }
+    {
+        cov_mark::check!(resolve_assoc_type_shorthand_qualifier);
+        check_no_base_items(
+            r#"
+trait B { type Y; fn f(); }
+trait A { type X: B; }
+fn f<T: A>(_: T::X::$0) {}
+"#,
+            expect![[r#"
+                as Y (as B) type Y
+            "#]],
+        );
+    }
+    cov_mark::check!(complete_assoc_const_in_generic_arg);
+    check_no_base_items(
+        r#"
+struct Arr<const N: usize>;
+trait B { const LEN: usize; type Y; fn f(); }
+trait A { type X: B; }
+fn f<T: A>() -> Arr<T::X::$0> {}
+"#,
+        expect![[r#"
+            ct LEN (as B) const LEN: usize
+            as Y (as B)   type Y
+        "#]],
+    );
+}
+
+#[test]
+fn type_position_completions_sink_stops_early() {
+    struct FirstN(Vec<CompletionItem>, usize);
+    impl CompletionSink for FirstN {
+        fn accept(&mut self, item: CompletionItem) {
+            assert!(!self.is_saturated(), "saturated sink was given more items");
+            self.0.push(item);
+        }
+        fn is_saturated(&self) -> bool {
+            self.0.len() >= self.1
+        }
+    }
+
+    let structs = (0..50).map(|idx| format!("struct S{};\n", idx)).collect::<String>();
+    let (db, position) = position(&format!("{}fn f(_: $0) {{}}", structs));
+    let all = completions_in_type_position(&db, &TEST_CONFIG, position);
+    assert!(all.len() > 50);
+
+    cov_mark::check!(type_path_stops_at_saturated_sink);
+    let mut sink = FirstN(Vec::new(), 10);
+    completions_in_type_position_with_sink(&db, &TEST_CONFIG, position, &mut sink);
+    assert_eq!(
+        sink.0.iter().map(|it| it.label()).collect::<Vec<_>>(),
+        all.iter().take(10).map(|it| it.label()).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn completes_assoc_items_of_self_in_default_trait_method_body() {
+    cov_mark::check!(complete_assoc_items_of_trait_self);
+    check_no_base_items(
+        r#"
+trait T {
+    type X;
+    fn f() { let _: Self::$0; }
+}
+"#,
+        expect![[r#"
+            as X (as T) type X
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_after_as_in_cast_expr() {
+    let items = get_all_items(TEST_CONFIG, "struct S;\nfn f() { let x = 0u8 as $0; }", None);
+    let labels = items.iter().map(|it| it.label()).collect::<Vec<_>>();
+    for label in ["u32", "usize", "i64", "f32", "char", "S"] {
+        assert!(labels.contains(&label), "{} is missing in {:?}", label, labels);
+    }
+}
+
+#[test]
+fn completes_assoc_types_of_self_in_trait_method_signature() {
+    // `Self` of a trait definition is its implicit type parameter, not an impl's self type.
+    cov_mark::check!(complete_assoc_items_of_trait_self);
+    check_no_base_items(
+        r#"
+trait Container {
+    type Item;
+    fn get(&self) -> Self::$0 where Self::Item: Clone;
+}
+"#,
+        expect![[r#"
+            as Item (as Container) type Item
+        "#]],
+    );
+}
+
+#[test]
+fn marks_sealed_traits_of_other_crates() {
+    check_no_base_items(
+        r#"
+//- /lib.rs crate:main deps:dep
+use dep::{Open, Reexported, Sealed};
+trait Local: Sealed {}
+fn f<T: $0>() {}
+//- /dep.rs crate:dep
+mod private { pub trait Sealed {} }
+mod public { pub trait Super {} }
+pub use public::Super;
+pub trait Sealed: private::Sealed {}
+pub trait Open: public::Super {}
+pub(crate) trait Hidden: private::Sealed {}
+mod reexported { pub trait Reexported: crate::private::Sealed {} }
+pub use reexported::Reexported;
+"#,
+        expect![[r#"
+            md dep
+            tt Local
+            tt Open
+            tt Reexported sealed
+            tt Sealed     sealed
+            kw ?Sized
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_consts_of_element_type_in_array_len() {
+    cov_mark::check!(complete_array_len_assoc_const);
+    check_no_base_items(
+        r#"
+struct MyStruct;
+impl MyStruct {
+    const LEN: usize = 4;
+    const NAME: &'static str = "";
+}
+struct S { f: [MyStruct; $0] }
+"#,
+        expect![[r#"
+            ct MyStruct::LEN const LEN: usize
+            sp Self
+            st MyStruct
+            st S
+            bt u32
+            kw crate::
+            kw false
+            kw for
+            kw if
+            kw if let
+            kw loop
+            kw match
+            kw self::
+            kw super::
+            kw true
+            kw unsafe
+            kw while
+            kw while let
+        "#]],
+    );
+    check_edit(
+        "MyStruct::LEN",
+        r#"
+struct MyStruct;
+impl MyStruct { const LEN: usize = 4; }
+fn f(_: [MyStruct; L$0]) {}
+"#,
+        r#"
+struct MyStruct;
+impl MyStruct { const LEN: usize = 4; }
+fn f(_: [MyStruct; MyStruct::LEN]) {}
+"#,
+    );
+}
+
+#[test]
+fn completes_trait_aliases_in_bounds() {
+    const FIXTURE: &str = r#"
+#![feature(trait_alias)]
+trait Bar {}
+trait Baz {}
+trait Foo = Bar + Baz;
+"#;
+    for (position, expect) in [
+        (
+            "fn f(_: impl $0) {}",
+            expect![[r#"
+                tt Bar
+                tt Baz
+                tr Foo
+                kw crate::
+                kw self::
+                kw super::
+                sn for<'a>
+            "#]],
+        ),
+        (
+            "fn f(_: &dyn $0) {}",
+            expect![[r#"
+                tt Bar
+                tt Baz
+                tr Foo
+                kw crate::
+                kw self::
+                kw super::
+                sn for<'a>
+            "#]],
+        ),
+        (
+            "fn f<T>() where T: $0 {}",
+            expect![[r#"
+                tt Bar
+                tt Baz
+                tr Foo
+                kw ?Sized
+                kw crate::
+                kw self::
+                kw super::
+                sn for<'a>
+            "#]],
+        ),
+    ] {
+        check_no_base_items(&format!("{}{}", FIXTURE, position), expect);
+    }
+}
+
+#[test]
+fn completes_ambiguous_glob_imports_qualified() {
+    const FIXTURE: &str = r#"
+mod io { pub struct Error; }
+mod fmt { pub struct Error; pub struct Formatter; }
+use io::*;
+use fmt::*;
+fn f(_: Err$0) {}
+"#;
+    {
+        cov_mark::check!(complete_ambiguous_glob_import);
+        check_no_base_items(
+            FIXTURE,
+            expect![[r#"
+                md fmt
+                md io
+                st Error                  fmt::Error
+                st Error                  io::Error
+                st Error (use fmt::Error)
+                st Formatter
+                bt u32
+                kw crate::
+                kw dyn
+                kw self::
+                kw super::
+                sn array                  [T; N]
+                sn tuple                  (T, U)
+            "#]],
+        );
+    }
+    check_edit(
+        "io::Error",
+        FIXTURE,
+        r#"
+mod io { pub struct Error; }
+mod fmt { pub struct Error; pub struct Formatter; }
+use io::*;
+use fmt::*;
+fn f(_: io::Error) {}
+"#,
+    );
+    // A name declared in the module itself shadows the glob imports.
+    check_no_base_items(
+        r#"
+mod io { pub struct Error; }
+mod fmt { pub struct Error; }
+use io::*;
+use fmt::*;
+struct Error;
+fn f(_: Err$0) {}
+"#,
+        expect![[r#"
+            md fmt
+            md io
+            st Error
+            st Error (use fmt::Error)
+            st Error (use io::Error)
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array                  [T; N]
+            sn tuple                  (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_in_where_pred_eq_rhs() {
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+struct S;
+fn f<T: Iterator>() where T::Item = $0 {}
+"#,
+        expect![[r#"
+            en Option<…>
+            md core
+            st S
+            tt IntoIterator
+            tt Iterator
+            tp T
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array        [T; N]
+            sn tuple        (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_build_dependencies_in_build_scripts() {
+    // Build scripts are crates of their own, depending on the build-dependencies of the package,
+    // so the dependency edges of the crate graph already only contain these.
+    check_no_base_items(
+        r#"
+//- /build.rs crate:build_script_build deps:cc
+fn main() { let _: $0; }
+//- /lib.rs crate:foo deps:dep
+pub struct Foo;
+//- /cc/lib.rs crate:cc
+pub struct Build;
+//- /dep/lib.rs crate:dep
+pub struct Dep;
+"#,
+        expect![[r#"
+            md cc
+            bt u32
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+//- /build.rs crate:build_script_build deps:cc
+fn main() { let _: cc::$0; }
+//- /cc/lib.rs crate:cc
+pub struct Build;
+"#,
+        expect![[r#"
+            st Build
+        "#]],
+    );
+}
+
+#[test]
+fn no_types_of_doc_hidden_dependency_crates() {
+    check_no_base_items(
+        r#"
+//- /main.rs crate:main deps:dep
+fn main() { let _: dep::$0; }
+//- /dep/lib.rs crate:dep
+#![doc(hidden)]
+pub struct Dep;
+"#,
+        expect![[""]],
+    );
+}
+
+#[test]
+fn completes_types_in_expr_turbofish() {
+    check_no_base_items(
+        r#"
+struct Vec<T>(T);
+impl<T> Vec<T> { fn new() -> Self { loop {} } }
+struct S;
+const C: usize = 0;
+fn f() { Vec::<$0>::new(); }
+"#,
+        expect![[r#"
+            ct C
+            st S
+            st Vec<…>
+            bt u32
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+struct Arr<const N: usize>;
+impl<const N: usize> Arr<N> { fn new() -> Self { loop {} } }
+const C: usize = 0;
+fn f() { Arr::<$0>::new(); }
+"#,
+        expect![[r#"
+            ct C
+            st Arr
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array       [T; N]
+            sn const { … }
+            sn tuple       (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_macro_generated_assoc_types() {
+    check_no_base_items(
+        r#"
+macro_rules! assoc { ($name:ident) => { type $name = u32; } }
+struct Ty;
+impl Ty { assoc!(X); }
+fn f(_: Ty::$0) {}
+"#,
+        expect![[r#"
+            as X type X = u32
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_reachable_through_supertraits_once() {
+    check(
+        r#"
+trait PartialEq { type Eq; }
+trait PartialOrd: PartialEq { type Ord; }
+trait Ord: PartialOrd {}
+fn f<T: Ord + PartialEq>(_: T::$0) {}
+"#,
+        expect![[r#"
+            as Eq (as PartialEq)   type Eq
+            as Ord (as PartialOrd) type Ord
+        "#]],
+    );
+    check(
+        r#"
+trait A { type X; }
+trait B: A {}
+trait C: A {}
+fn f<T: B + C>() { T::$0 }
+"#,
+        expect![[r#"
+            as X (as A) type X
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_impl_trait_return_type_bounds() {
+    // Like opaque parameter types, opaque return types can only be projected through their bounds.
+    check_no_base_items(
+        r#"
+//- minicore: iterator
+fn f() -> impl Iterator<$0> { loop {} }
+"#,
+        expect![[r#"
+            en Option<…>
+            md core
+            tt IntoIterator
+            tt Iterator
+            as Item =  (as Iterator) pub type Item
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array                 [T; N]
+            sn tuple                 (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+trait Super { type Super; }
+trait Trait: Super { type Assoc; }
+fn f() -> impl Trait { loop {} }
+fn g() {
+    let _: <_ as Trait>::$0 = f();
+}
+"#,
+        expect![[r#"
+            as Assoc (as Trait) type Assoc
+            as Super (as Super) type Super
+        "#]],
+    );
+}
+
+#[test]
+fn completes_traits_in_impl_header_after_generic_params() {
+    check_no_base_items(
+        r#"
+trait Tr<T> {}
+trait Plain {}
+struct Vec<T>(T);
+impl<T> $0 for Vec<T> {}
+"#,
+        expect![[r#"
+            st Vec<…>
+            tt Plain
+            tt Tr<…>
+            bt u32
+            kw crate::
+            kw self::
+            kw super::
+        "#]],
+    );
+    // The generic parameters of the impl can be used in the arguments of the trait.
+    check_no_base_items(
+        r#"
+trait Tr<T> {}
+struct Vec<T>(T);
+impl<T> Tr<$0> for Vec<T> {}
+"#,
+        expect![[r#"
+            st Vec<…>
+            tp T
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_only_unbound_assoc_types_in_generics_list() {
+    cov_mark::check!(skip_bound_assoc_type_in_generics_list);
+    check_no_base_items(
+        r#"
+trait Trait { type Assoc; type Other; }
+fn f(_: &dyn Trait<Assoc = u32, $0>) {}
+"#,
+        expect![[r#"
+            tt Trait
+            as Other =  (as Trait) type Other
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array               [T; N]
+            sn tuple               (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_absolute_paths_by_edition() {
+    check_no_base_items(
+        r#"
+//- /main.rs crate:main deps:dep edition:2018
+struct RootStruct;
+mod m { fn f(_: ::$0) {} }
+//- /dep.rs crate:dep
+pub struct Dep;
+"#,
+        expect![[r#"
+            md dep
+        "#]],
+    );
+    cov_mark::check!(complete_crate_root_items_in_2015_absolute_path);
+    check_no_base_items(
+        r#"
+//- /main.rs crate:main deps:dep edition:2015
+extern crate dep;
+struct RootStruct;
+mod m { fn f(_: ::$0) {} }
+//- /dep.rs crate:dep
+pub struct Dep;
+"#,
+        expect![[r#"
+            md dep
+            md m
+            st RootStruct
+        "#]],
+    );
+}
+
+#[test]
+fn completes_only_auto_traits_after_dyn_principal_trait() {
+    cov_mark::check!(only_auto_traits_after_dyn_principal);
+    check_no_base_items(
+        r#"
+trait Error {}
+unsafe auto trait Send {}
+unsafe auto trait Sync {}
+struct Box<T: ?Sized>(T);
+fn f(_: Box<dyn Error + $0>) {}
+"#,
+        expect![[r#"
+            lt 'static
+            tt Send
+            tt Sync
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+    // Without a principal trait yet, it can still follow the auto traits.
+    check_no_base_items(
+        r#"
+trait Error {}
+unsafe auto trait Send {}
+struct Box<T: ?Sized>(T);
+fn f(_: Box<dyn Send + $0>) {}
+"#,
+        expect![[r#"
+            tt Error
+            tt Send
+            kw crate::
+            kw self::
+            kw super::
+            sn for<'a>
+        "#]],
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_unimported_bound_traits() {
+    // Shorthand projections are resolved through the bounds of the type parameter rather than the
+    // traits in scope, so the trait doesn't have to be imported for `T::Assoc` to work.
+    check_edit(
+        "Assoc (as SomeTrait)",
+        r#"
+mod m { pub trait SomeTrait { type Assoc; } }
+fn f<T: m::SomeTrait>(_: T::$0) {}
+"#,
+        r#"
+mod m { pub trait SomeTrait { type Assoc; } }
+fn f<T: m::SomeTrait>(_: T::Assoc) {}
+"#,
+    );
+}
+
+#[test]
+fn completes_assoc_types_of_self_in_trait_assoc_const_type() {
+    cov_mark::check!(complete_assoc_items_of_trait_self);
+    check_no_base_items(
+        r#"
+trait T {
+    type Assoc;
+    const C: Self::$0;
+}
+"#,
+        expect![[r#"
+            as Assoc (as T) type Assoc
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_in_fn_ptr_params() {
+    check_no_base_items(
+        r#"
+struct S;
+fn f() { let f: fn($0) -> u32; }
+"#,
+        expect![[r#"
+            st S
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+    check_no_base_items(
+        r#"
+struct S;
+fn f() { let f: extern "C" fn(u8, $0); }
+"#,
+        expect![[r#"
+            st S
+            bt u32
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array   [T; N]
+            sn tuple   (T, U)
+        "#]],
+    );
+}
+
+#[test]
+fn completes_types_in_pat_turbofish() {
+    check_no_base_items(
+        r#"
+struct Wrapper<T>(T);
+struct S;
+fn f(e: Wrapper<S>) { if let Wrapper::<$0>(x) = e {} }
+"#,
+        expect![[r#"
+            st S
+            st Wrapper<…>
+            bt u32
+            kw _
+            kw crate::
+            kw dyn
+            kw self::
+            kw super::
+            sn array      [T; N]
+            sn tuple      (T, U)
         "#]],
     );
 }
@@ -55,6 +55,24 @@ mod foo {}
     );
 }
 
+#[test]
+fn use_tree_start_abs_dedups_crate_roots() {
+    check(
+        r#"
+//- /lib.rs crate:main deps:other_crate
+extern crate other_crate as renamed;
+extern crate self as this;
+use ::$0
+//- /other_crate/lib.rs crate:other_crate
+// nothing here
+"#,
+        expect![[r#"
+            md other_crate
+            md this
+        "#]],
+    );
+}
+
 #[test]
 fn dont_complete_current_use() {
     cov_mark::check!(dont_complete_current_use);
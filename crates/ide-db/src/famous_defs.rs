@@ -1,7 +1,7 @@
 //! See [`FamousDefs`].
 
 use base_db::{CrateOrigin, LangCrateOrigin, SourceDatabase};
-use hir::{Crate, Enum, Macro, Module, ScopeDef, Semantics, Trait};
+use hir::{Adt, Crate, Enum, Macro, Module, ScopeDef, Semantics, Trait};
 
 use crate::RootDatabase;
 
@@ -102,10 +102,22 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:marker:Copy")
     }
 
+    pub fn core_marker_Sized(&self) -> Option<Trait> {
+        self.find_trait("core:marker:Sized")
+    }
+
+    pub fn core_marker_PhantomData(&self) -> Option<Adt> {
+        self.find_adt("core:marker:PhantomData")
+    }
+
     pub fn core_macros_builtin_derive(&self) -> Option<Macro> {
         self.find_macro("core:macros:builtin:derive")
     }
 
+    pub fn std_error_Error(&self) -> Option<Trait> {
+        self.find_trait("std:error:Error")
+    }
+
     pub fn builtin_crates(&self) -> impl Iterator<Item = Crate> {
         IntoIterator::into_iter([
             self.std(),
@@ -138,6 +150,13 @@ impl FamousDefs<'_, '_> {
         }
     }
 
+    fn find_adt(&self, path: &str) -> Option<Adt> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(it)) => Some(it),
+            _ => None,
+        }
+    }
+
     fn find_module(&self, path: &str) -> Option<Module> {
         match self.find_def(path)? {
             hir::ScopeDef::ModuleDef(hir::ModuleDef::Module(it)) => Some(it),
@@ -399,6 +399,9 @@ fn highlight_def(sema: &Semantics<RootDatabase>, krate: hir::Crate, def: Definit
 
             h
         }
+        Definition::Trait(trait_) if trait_.is_alias(db) => {
+            Highlight::new(HlTag::Symbol(SymbolKind::TraitAlias))
+        }
         Definition::Trait(_) => Highlight::new(HlTag::Symbol(SymbolKind::Trait)),
         Definition::TypeAlias(type_) => {
             let mut h = Highlight::new(HlTag::Symbol(SymbolKind::TypeAlias));
@@ -233,6 +233,12 @@ fn where_predicate(p: &mut Parser) {
 
             if p.at(T![:]) {
                 bounds(p);
+            } else if p.at(T![=]) {
+                // test_err where_pred_eq
+                // fn f<T: Iterator>() where T::Item = u32 {}
+                p.error("equality constraints are not supported in where clauses");
+                p.bump(T![=]);
+                types::type_(p);
             } else {
                 p.error("expected colon");
             }
@@ -0,0 +1 @@
+fn f<T: Iterator>() where T::Item = u32 {}
@@ -136,10 +136,14 @@ config_data! {
         completion_autoself_enable: bool        = "true",
         /// Whether to add parenthesis and argument snippets when completing function.
         completion_callable_snippets: CallableCompletionDef  = "\"fill_arguments\"",
+        /// Whether to also offer the inferred type of a `let` binding wrapped in the `Box`, `Rc` and `Arc` smart pointers that are in scope.
+        completion_inferredType_smartPointers_enable: bool = "false",
         /// Whether to show postfix snippets like `dbg`, `if`, `not`, etc.
         completion_postfix_enable: bool         = "true",
         /// Enables completions of private items and fields that are defined in the current workspace even if they are not visible at the current position.
         completion_privateEditable_enable: bool = "false",
+        /// Whether to also complete the types of the current crate that are not in scope but reachable through a `crate::` path, like the `pub(crate)` types of sibling modules, adding a `use` of that path.
+        completion_privateItemsInCrate_enable: bool = "false",
         /// Custom completion snippets.
         // NOTE: Keep this list in sync with the feature docs of user snippets.
         completion_snippets_custom: FxHashMap<String, SnippetDef> = r#"{
@@ -1073,6 +1077,10 @@ impl Config {
                 && completion_item_edit_resolve(&self.caps),
             enable_self_on_the_fly: self.data.completion_autoself_enable,
             enable_private_editable: self.data.completion_privateEditable_enable,
+            enable_private_items_in_crate: self.data.completion_privateItemsInCrate_enable,
+            enable_smart_pointer_inferred_types: self
+                .data
+                .completion_inferredType_smartPointers_enable,
             callable: match self.data.completion_callable_snippets {
                 CallableCompletionDef::FillArguments => Some(CallableSnippets::FillArguments),
                 CallableCompletionDef::AddParentheses => Some(CallableSnippets::AddParentheses),
@@ -1090,6 +1098,8 @@ impl Config {
                     .snippet_support?
             )),
             snippets: self.snippets.clone(),
+            excluded_crates: Vec::new(),
+            recently_used_types: Vec::new(),
         }
     }
 
@@ -70,6 +70,7 @@ define_semantic_token_types![
     (SEMICOLON, "semicolon"),
     (TYPE_ALIAS, "typeAlias"),
     (TOOL_MODULE, "toolModule"),
+    (TRAIT_ALIAS, "traitAlias"),
     (UNION, "union"),
     (UNRESOLVED_REFERENCE, "unresolvedReference"),
 ];
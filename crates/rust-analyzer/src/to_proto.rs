@@ -49,7 +49,7 @@ pub(crate) fn symbol_kind(symbol_kind: SymbolKind) -> lsp_types::SymbolKind {
         SymbolKind::Struct => lsp_types::SymbolKind::STRUCT,
         SymbolKind::Enum => lsp_types::SymbolKind::ENUM,
         SymbolKind::Variant => lsp_types::SymbolKind::ENUM_MEMBER,
-        SymbolKind::Trait => lsp_types::SymbolKind::INTERFACE,
+        SymbolKind::Trait | SymbolKind::TraitAlias => lsp_types::SymbolKind::INTERFACE,
         SymbolKind::Macro
         | SymbolKind::BuiltinAttr
         | SymbolKind::Attribute
@@ -105,6 +105,7 @@ pub(crate) fn completion_item_kind(
     completion_item_kind: CompletionItemKind,
 ) -> lsp_types::CompletionItemKind {
     match completion_item_kind {
+        CompletionItemKind::AssocType => lsp_types::CompletionItemKind::TYPE_PARAMETER,
         CompletionItemKind::Binding => lsp_types::CompletionItemKind::VARIABLE,
         CompletionItemKind::BuiltinType => lsp_types::CompletionItemKind::STRUCT,
         CompletionItemKind::InferredType => lsp_types::CompletionItemKind::SNIPPET,
@@ -130,7 +131,7 @@ pub(crate) fn completion_item_kind(
             SymbolKind::SelfType => lsp_types::CompletionItemKind::TYPE_PARAMETER,
             SymbolKind::Static => lsp_types::CompletionItemKind::VALUE,
             SymbolKind::Struct => lsp_types::CompletionItemKind::STRUCT,
-            SymbolKind::Trait => lsp_types::CompletionItemKind::INTERFACE,
+            SymbolKind::Trait | SymbolKind::TraitAlias => lsp_types::CompletionItemKind::INTERFACE,
             SymbolKind::TypeAlias => lsp_types::CompletionItemKind::STRUCT,
             SymbolKind::TypeParam => lsp_types::CompletionItemKind::TYPE_PARAMETER,
             SymbolKind::Union => lsp_types::CompletionItemKind::STRUCT,
@@ -588,6 +589,7 @@ fn semantic_token_type_and_modifiers(
             SymbolKind::Union => semantic_tokens::UNION,
             SymbolKind::TypeAlias => semantic_tokens::TYPE_ALIAS,
             SymbolKind::Trait => lsp_types::SemanticTokenType::INTERFACE,
+            SymbolKind::TraitAlias => semantic_tokens::TRAIT_ALIAS,
             SymbolKind::Macro => lsp_types::SemanticTokenType::MACRO,
             SymbolKind::BuiltinAttr => semantic_tokens::BUILTIN_ATTRIBUTE,
             SymbolKind::ToolModule => semantic_tokens::TOOL_MODULE,
@@ -1373,6 +1375,18 @@ fn main() {
     }
 
     // `Url` is not able to parse windows paths on unix machines.
+    #[test]
+    fn assoc_types_and_type_aliases_have_distinct_completion_kinds() {
+        assert_eq!(
+            completion_item_kind(CompletionItemKind::AssocType),
+            lsp_types::CompletionItemKind::TYPE_PARAMETER
+        );
+        assert_eq!(
+            completion_item_kind(CompletionItemKind::SymbolKind(SymbolKind::TypeAlias)),
+            lsp_types::CompletionItemKind::STRUCT
+        );
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn test_lowercase_drive_letter() {
@@ -37,6 +37,7 @@
 //!     add:
 //!     as_ref: sized
 //!     drop:
+//!     phantom_data: sized
 
 pub mod marker {
     // region:sized
@@ -46,6 +47,11 @@ pub mod marker {
     pub trait Sized {}
     // endregion:sized
 
+    // region:phantom_data
+    #[lang = "phantom_data"]
+    pub struct PhantomData<T: ?Sized>;
+    // endregion:phantom_data
+
     // region:unsize
     #[lang = "unsize"]
     pub trait Unsize<T: ?Sized> {}